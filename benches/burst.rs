@@ -0,0 +1,50 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+//! Compares per-sample insertion against coalesced bursts.
+//! Run with `cargo bench --bench burst`.
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use emergency_brake::*;
+use std::hint::black_box;
+
+const OUTCOMES: usize = 100_000;
+const FLUSH_EVERY: usize = 10_000;
+
+fn outcome(i: usize) -> bool {
+    !i.is_multiple_of(97)
+}
+
+fn burst(c: &mut Criterion) {
+    let mut group = c.benchmark_group("burst");
+    group.throughput(Throughput::Elements(OUTCOMES as u64));
+    group.bench_function("add_sample", |b| {
+        b.iter(|| {
+            let mut ebrake = EBrake::new(100, 10);
+            for i in 0..OUTCOMES {
+                ebrake.add_sample(black_box(outcome(i)));
+            }
+            black_box(ebrake.should_trigger())
+        })
+    });
+    group.bench_function("add_burst", |b| {
+        b.iter(|| {
+            let mut ebrake = EBrake::new(100, 10);
+            let mut sampler = BurstSampler::new();
+            for i in 0..OUTCOMES {
+                sampler.record(black_box(outcome(i)));
+                if sampler.pending() == FLUSH_EVERY {
+                    sampler.flush(&mut ebrake);
+                }
+            }
+            sampler.flush(&mut ebrake);
+            black_box(ebrake.should_trigger())
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, burst);
+criterion_main!(benches);
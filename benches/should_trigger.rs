@@ -0,0 +1,58 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+//! Measures `should_trigger` on a warmed-up window, the common case in hot
+//! sampling loops. Run with `cargo bench --bench should_trigger`. To compare
+//! against an earlier revision, run it there with `-- --save-baseline before`
+//! and then here with `-- --baseline before`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use emergency_brake::*;
+use std::hint::black_box;
+
+const WINDOW: usize = 64;
+const TOLERANCE: usize = 8;
+
+/// Returns whether the sample at `index` succeeds, failing one in sixteen.
+fn outcome(index: usize) -> bool {
+    !index.is_multiple_of(16)
+}
+
+fn should_trigger(c: &mut Criterion) {
+    let mut fixed = EBrakeN::<WINDOW>::new(TOLERANCE);
+    let mut dynamic = EBrake::new(WINDOW, TOLERANCE);
+    for index in 0..WINDOW {
+        fixed.add_sample(outcome(index));
+        dynamic.add_sample(outcome(index));
+    }
+
+    let mut group = c.benchmark_group("should_trigger");
+    group.bench_function("EBrakeN", |b| b.iter(|| black_box(&fixed).should_trigger()));
+    group.bench_function("EBrake", |b| b.iter(|| black_box(&dynamic).should_trigger()));
+    group.finish();
+}
+
+fn trigger_on_sample(c: &mut Criterion) {
+    let mut fixed = EBrakeN::<WINDOW>::new(TOLERANCE);
+    let mut dynamic = EBrake::new(WINDOW, TOLERANCE);
+    let mut group = c.benchmark_group("trigger_on_sample");
+    let mut index = 0usize;
+    group.bench_function("EBrakeN", |b| {
+        b.iter(|| {
+            index = index.wrapping_add(1);
+            fixed.trigger_on_sample(black_box(outcome(index)), &Trigger::Log)
+        })
+    });
+    group.bench_function("EBrake", |b| {
+        b.iter(|| {
+            index = index.wrapping_add(1);
+            dynamic.trigger_on_sample(black_box(outcome(index)), &Trigger::Log)
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, should_trigger, trigger_on_sample);
+criterion_main!(benches);
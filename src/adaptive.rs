@@ -0,0 +1,140 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+/// The failure rate a window may reach under the adaptive tolerance however
+/// low the baseline, so a calibration without failures does not trip on the
+/// first failure.
+pub const DEFAULT_ADAPTIVE_MIN_FAILURE_RATE: f64 = 0.1;
+
+/// Returns the default minimum failure rate, for brakes saved without one.
+#[cfg(feature = "serde")]
+fn default_min_failure_rate() -> f64 {
+    DEFAULT_ADAPTIVE_MIN_FAILURE_RATE
+}
+
+/// The baseline failure rate learned during calibration, and the margin by
+/// which the live failure rate may exceed it.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct AdaptiveTolerance {
+    calibration_samples: usize,
+    margin: f64,
+    #[cfg_attr(feature = "serde", serde(default = "default_min_failure_rate"))]
+    min_failure_rate: f64,
+    observed: usize,
+    observed_failures: usize,
+}
+
+impl AdaptiveTolerance {
+    /// Counts a sample towards the baseline until calibration is complete.
+    pub(crate) fn observe(&mut self, sample: bool) {
+        if self.observed < self.calibration_samples {
+            self.observed += 1;
+            if !sample {
+                self.observed_failures += 1;
+            }
+        }
+    }
+
+    /// Counts `count` identical samples towards the baseline at once.
+    pub(crate) fn observe_many(&mut self, sample: bool, count: usize) {
+        let counted = count.min(self.calibration_samples - self.observed);
+        self.observed += counted;
+        if !sample {
+            self.observed_failures += counted;
+        }
+    }
+
+    /// Returns the baseline failure rate once calibration is complete.
+    fn baseline(&self) -> Option<f64> {
+        (self.observed == self.calibration_samples).then(|| self.observed_failures as f64 / self.calibration_samples as f64)
+    }
+
+    /// Returns true if the failure rate of a window exceeds the baseline by
+    /// more than the margin, and exceeds the minimum failure rate.
+    pub(crate) fn exceeded(&self, failures: usize, len: usize) -> bool {
+        self.baseline().is_some_and(|baseline| len > 0 && failures as f64 / len as f64 > (baseline * self.margin).max(self.min_failure_rate))
+    }
+}
+
+impl EBrake {
+    /// Enables the adaptive tolerance, which replaces the failure threshold.
+    /// The first `calibration_samples` samples are used to learn the normal
+    /// failure rate, during which the brake does not trip on its window. Once
+    /// calibrated, the brake trips when the failure rate of the window exceeds
+    /// the baseline multiplied by `margin`, so a margin of `3.0` allows three
+    /// times the normal failure rate. The window may always reach
+    /// `DEFAULT_ADAPTIVE_MIN_FAILURE_RATE`, so a baseline of zero does not
+    /// trip on the first failure; see `set_adaptive_min_failure_rate`.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(10, 0);
+    /// ebrake.set_adaptive_tolerance(20, 3.0).unwrap();
+    /// for i in 0..20 {
+    ///     ebrake.add_sample(i % 10 != 0);
+    /// }
+    /// assert_eq!(ebrake.baseline_failure_rate(), Some(0.1));
+    /// assert!(!ebrake.should_trigger());
+    /// ebrake.add_samples(&[false, false, false, false]);
+    /// assert!(ebrake.should_trigger());
+    /// ```
+    pub fn set_adaptive_tolerance(&mut self, calibration_samples: usize, margin: f64) -> Result<(), EBrakeError> {
+        if calibration_samples == 0 || !(margin > 0.0 && margin.is_finite()) {
+            return Err(EBrakeError::InvalidCalibration);
+        }
+
+        self.adaptive = Some(AdaptiveTolerance {
+            calibration_samples,
+            margin,
+            min_failure_rate: DEFAULT_ADAPTIVE_MIN_FAILURE_RATE,
+            observed: 0,
+            observed_failures: 0,
+        });
+        Ok(())
+    }
+
+    /// Sets the failure rate a window may reach under the adaptive tolerance
+    /// however low the baseline, replacing `DEFAULT_ADAPTIVE_MIN_FAILURE_RATE`.
+    /// A rate of `0.0` trips on any failure over a baseline of zero. Returns
+    /// an error if the adaptive tolerance is not enabled, or if the rate is
+    /// not within `[0.0, 1.0]`.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(10, 0);
+    /// ebrake.set_adaptive_tolerance(10, 2.0).unwrap();
+    /// ebrake.set_adaptive_min_failure_rate(0.2).unwrap();
+    /// ebrake.extend([true; 10]);
+    /// ebrake.extend([false, false]);
+    /// assert!(!ebrake.should_trigger());
+    /// ebrake.add_sample(false);
+    /// assert!(ebrake.should_trigger());
+    /// ```
+    pub fn set_adaptive_min_failure_rate(&mut self, min_failure_rate: f64) -> Result<(), EBrakeError> {
+        match &mut self.adaptive {
+            Some(adaptive) if (0.0..=1.0).contains(&min_failure_rate) => {
+                adaptive.min_failure_rate = min_failure_rate;
+                Ok(())
+            },
+            _ => Err(EBrakeError::InvalidCalibration),
+        }
+    }
+
+    /// Returns the baseline failure rate learned by the adaptive tolerance,
+    /// or `None` if it is disabled or still calibrating.
+    pub fn baseline_failure_rate(&self) -> Option<f64> {
+        self.adaptive.as_ref().and_then(AdaptiveTolerance::baseline)
+    }
+
+    /// Returns true while the adaptive tolerance is learning its baseline.
+    pub fn is_calibrating(&self) -> bool {
+        self.adaptive.as_ref().is_some_and(|adaptive| adaptive.baseline().is_none())
+    }
+}
+
+/// Test module for the adaptive tolerance.
+#[cfg(test)]
+mod test;
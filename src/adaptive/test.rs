@@ -0,0 +1,116 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+#[test]
+/// Test that a spike after a stable baseline period trips the brake.
+fn it_should_trip_on_a_spike_over_the_baseline() {
+    let mut ebrake = EBrake::new(10, 0);
+    ebrake.set_adaptive_tolerance(50, 2.0).unwrap();
+    for i in 0..50 {
+        ebrake.add_sample(i % 5 != 0);
+        assert!(!ebrake.should_trigger());
+    }
+    assert!(!ebrake.is_calibrating());
+    assert_eq!(ebrake.baseline_failure_rate(), Some(0.2));
+
+    // The same failure rate as the baseline is normal.
+    for i in 0..20 {
+        ebrake.add_sample(i % 5 != 0);
+        assert!(!ebrake.should_trigger());
+    }
+
+    // Twice the baseline is still within the margin.
+    ebrake.extend([false, false, true, false]);
+    assert_eq!(ebrake.failure_rate(), 0.4);
+    assert!(!ebrake.should_trigger());
+
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.failure_rate(), 0.5);
+    assert!(ebrake.should_trigger());
+}
+
+#[test]
+/// Test that the baseline is only learned once calibration is complete.
+fn it_should_not_trip_while_calibrating() {
+    let mut ebrake = EBrake::new(4, 0);
+    ebrake.set_adaptive_tolerance(8, 1.5).unwrap();
+    ebrake.extend([false; 7]);
+    assert!(ebrake.is_calibrating());
+    assert_eq!(ebrake.baseline_failure_rate(), None);
+    assert!(!ebrake.should_trigger());
+
+    ebrake.add_sample(true);
+    assert_eq!(ebrake.baseline_failure_rate(), Some(0.875));
+    assert!(!ebrake.should_trigger());
+}
+
+#[test]
+/// Test that a baseline without failures does not trip on the first failure.
+fn it_should_not_trip_on_one_failure_over_a_zero_baseline() {
+    let mut ebrake = EBrake::new(10, 3);
+    ebrake.set_adaptive_tolerance(10, 3.0).unwrap();
+    ebrake.add_samples(&[true; 10]);
+    assert_eq!(ebrake.baseline_failure_rate(), Some(0.0));
+    ebrake.add_sample(false);
+    assert!(!ebrake.should_trigger());
+
+    // The failures must still exceed the minimum failure rate.
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.failure_rate(), 0.2);
+    assert!(ebrake.should_trigger());
+}
+
+#[test]
+/// Test that a minimum failure rate of zero trips on any failure over a zero baseline.
+fn it_should_trip_on_any_failure_without_a_minimum_rate() {
+    let mut ebrake = EBrake::new(5, 3);
+    ebrake.set_adaptive_tolerance(5, 3.0).unwrap();
+    ebrake.set_adaptive_min_failure_rate(0.0).unwrap();
+    ebrake.add_samples(&[true; 5]);
+    ebrake.add_sample(false);
+    assert!(ebrake.should_trigger());
+}
+
+#[test]
+/// Test that an invalid minimum failure rate is rejected.
+fn it_should_reject_an_invalid_minimum_failure_rate() {
+    let mut ebrake = EBrake::new(5, 1);
+    assert_eq!(ebrake.set_adaptive_min_failure_rate(0.2), Err(EBrakeError::InvalidCalibration));
+    ebrake.set_adaptive_tolerance(5, 2.0).unwrap();
+    for rate in [-0.1, 1.5, f64::NAN] {
+        assert_eq!(ebrake.set_adaptive_min_failure_rate(rate), Err(EBrakeError::InvalidCalibration));
+    }
+    assert_eq!(ebrake.set_adaptive_min_failure_rate(1.0), Ok(()));
+}
+
+#[test]
+/// Test that an invalid calibration is rejected.
+fn it_should_reject_an_invalid_calibration() {
+    let mut ebrake = EBrake::new(5, 1);
+    assert_eq!(ebrake.set_adaptive_tolerance(0, 2.0), Err(EBrakeError::InvalidCalibration));
+    for margin in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        assert_eq!(ebrake.set_adaptive_tolerance(10, margin), Err(EBrakeError::InvalidCalibration));
+    }
+    assert_eq!(ebrake.baseline_failure_rate(), None);
+    assert!(!ebrake.is_calibrating());
+}
+
+#[test]
+/// Test that would_trigger counts the sample that completes calibration.
+fn it_should_predict_a_trip_at_the_end_of_calibration() {
+    let mut ebrake = EBrake::new(4, 0);
+    ebrake.set_adaptive_tolerance(4, 0.5).unwrap();
+    ebrake.extend([true, true, true]);
+    assert!(ebrake.is_calibrating());
+    assert!(!ebrake.would_trigger(true));
+    assert!(ebrake.would_trigger(false));
+    assert!(ebrake.is_calibrating());
+
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.baseline_failure_rate(), Some(0.25));
+    assert!(ebrake.should_trigger());
+}
@@ -0,0 +1,53 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+impl EBrake {
+    /// Disarms the emergency brake. Samples are still recorded and
+    /// `should_trigger` still reports the window, but the trigger methods only
+    /// log the trip and return without taking their action. This is useful to
+    /// avoid self-inflicted outages during planned maintenance.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(2, 0);
+    /// ebrake.disarm();
+    /// ebrake.add_samples(&[false, false]);
+    /// assert!(ebrake.should_trigger());
+    /// assert!(!ebrake.trigger_panic());
+    /// ```
+    pub fn disarm(&mut self) {
+        self.disarmed = true;
+    }
+
+    /// Arms the emergency brake again, so that the trigger methods take their
+    /// action once the brake has tripped. A new brake is armed.
+    pub fn arm(&mut self) {
+        self.disarmed = false;
+    }
+
+    /// Returns true unless the emergency brake has been disarmed.
+    pub fn is_armed(&self) -> bool {
+        !self.disarmed
+    }
+
+    /// Returns true if a trigger method should take its action. A disarmed
+    /// brake logs the trip instead, and a rate limited brake skips it silently.
+    pub(crate) fn should_fire(&self) -> bool {
+        if !self.should_trigger() {
+            return false;
+        }
+        if self.disarmed {
+            self.log_trip("Emergency brake triggered while disarmed!");
+            return false;
+        }
+
+        self.trigger_limit.as_ref().is_none_or(|limit| limit.allow(self.clock.now()))
+    }
+}
+
+/// Test module for arming and disarming the emergency brake.
+#[cfg(test)]
+mod test;
@@ -0,0 +1,49 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+use crate::test::capture_events;
+
+#[test]
+/// Test that a disarmed brake records samples but does not fire.
+fn it_should_not_fire_while_disarmed() {
+    let mut ebrake = EBrake::new(2, 0);
+    ebrake.disarm();
+    assert!(!ebrake.is_armed());
+    ebrake.extend([false, false]);
+    assert_eq!(ebrake.current_failures(), 2);
+    assert!(ebrake.should_trigger());
+
+    assert_eq!(ebrake.trigger(&Trigger::Panic), TripOutcome::NotTripped);
+    assert!(!ebrake.trigger_panic());
+    assert!(!ebrake.trigger_abort());
+}
+
+#[test]
+/// Test that a disarmed brake still logs the trip.
+fn it_should_log_the_trip_while_disarmed() {
+    let mut ebrake = EBrake::new(1, 0);
+    ebrake.disarm();
+    ebrake.add_sample(false);
+    let events = capture_events(|| {
+        ebrake.trigger(&Trigger::Log);
+    });
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].fields["message"], "Emergency brake triggered while disarmed!");
+}
+
+#[test]
+#[should_panic(expected = "Emergency brake panic triggered!")]
+/// Test that re-arming the brake restores firing.
+fn it_should_fire_once_rearmed() {
+    let mut ebrake = EBrake::new(1, 0);
+    ebrake.disarm();
+    ebrake.add_sample(false);
+    assert!(!ebrake.trigger_panic());
+
+    ebrake.arm();
+    assert!(ebrake.is_armed());
+    ebrake.trigger_panic();
+}
@@ -0,0 +1,63 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+use hooks::Hook;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// A boxed future returned by an asynchronous trigger action.
+type TriggerFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// The AsyncTrigger is a trigger action that is awaited, such as a shutdown
+/// routine that flushes async buffers or closes connection pools before the
+/// service stops. Cloning the trigger shares the same action.
+#[derive(Clone, Debug)]
+pub struct AsyncTrigger(Hook<dyn Fn() -> TriggerFuture + Send + Sync>);
+
+impl AsyncTrigger {
+    /// Creates a trigger from a closure returning the future to await when
+    /// the emergency brake is triggered.
+    pub fn new<F, Fut>(action: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        AsyncTrigger(Hook(Arc::new(move || Box::pin(action()) as TriggerFuture)))
+    }
+}
+
+impl EBrake {
+    /// Returns `TripOutcome::NotTripped` if the emergency brake has not been triggered.
+    /// If the emergency brake has been triggered, the asynchronous trigger
+    /// action is awaited, which makes it suitable for use from the tokio
+    /// service watcher or any other async task.
+    /// ```
+    /// use emergency_brake::*;
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let shutdown = AsyncTrigger::new(|| async { println!("draining connections") });
+    /// let mut ebrake = EBrake::new(2, 0);
+    /// ebrake.add_samples(&[true, false]);
+    /// assert_eq!(ebrake.trigger_async(&shutdown).await, TripOutcome::Handled);
+    /// # });
+    /// ```
+    pub async fn trigger_async(&self, trigger: &AsyncTrigger) -> TripOutcome {
+        match self.should_fire() {
+            true => {
+                self.log_trip("Emergency brake triggered!");
+                self.notify_trip();
+                (trigger.0)().await;
+                TripOutcome::Handled
+            },
+            false => TripOutcome::NotTripped,
+        }
+    }
+}
+
+/// Test module for the asynchronous trigger.
+#[cfg(test)]
+mod test;
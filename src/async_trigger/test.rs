@@ -0,0 +1,31 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+#[tokio::test]
+/// Test that the asynchronous action is awaited once the threshold is crossed.
+async fn it_should_await_the_trigger_action() {
+    let flushed = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&flushed);
+    let trigger = AsyncTrigger::new(move || {
+        let flag = Arc::clone(&flag);
+        async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            flag.store(true, Ordering::SeqCst);
+        }
+    });
+
+    let mut ebrake = EBrake::new(2, 0);
+    ebrake.add_sample(true);
+    assert_eq!(ebrake.trigger_async(&trigger).await, TripOutcome::NotTripped);
+    assert!(!flushed.load(Ordering::SeqCst));
+
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.trigger_async(&trigger).await, TripOutcome::Handled);
+    assert!(flushed.load(Ordering::SeqCst));
+}
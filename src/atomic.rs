@@ -0,0 +1,170 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+
+/// A slot in the ring that holds no sample yet.
+const EMPTY: u8 = 0;
+
+/// A slot in the ring that holds a success.
+const SUCCESS: u8 = 1;
+
+/// A slot in the ring that holds a failure.
+const FAILURE: u8 = 2;
+
+/// The AtomicEBrake is a lock-free emergency brake for hot paths.
+/// Samples are recorded through shared references into a fixed ring of atomic
+/// slots, each empty or holding a success or a failure, with an atomic head
+/// index that wraps around the ring.
+///
+/// # Consistency
+///
+/// Each operation is atomic on its own, but the counters and the ring are not
+/// updated as a single transaction. A writer counts its sample before storing
+/// it, and subtracts the sample it displaces after storing it, so a counter
+/// never underflows. While samples are being recorded or the ring is being
+/// reset concurrently, `current_failures`, `current_successes`, `len` and
+/// `should_trigger` may count up to one extra sample per writer in progress.
+/// Once concurrent writers are quiet, each counter equals the number of slots
+/// holding that outcome. When writers race for the same slot, the sample
+/// evicted may be a slightly newer one than the oldest.
+/// ```
+/// use emergency_brake::*;
+/// let ebrake = AtomicEBrake::new(10, 3);
+/// ebrake.add_sample(true);
+/// assert_eq!(ebrake.trigger(&Trigger::Panic), TripOutcome::NotTripped);
+/// ```
+#[derive(Debug)]
+pub struct AtomicEBrake {
+    data: Box<[AtomicU8]>,
+    failures: AtomicUsize,
+    head: AtomicUsize,
+    successes: AtomicUsize,
+    tolerance: usize,
+}
+
+impl AtomicEBrake {
+    /// Creates a new atomic Emergency Brake with the given number of samples and tolerance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `samples` is zero.
+    pub fn new(samples: usize, tolerance: usize) -> Self {
+        assert!(samples > 0, "sample window size must be greater than zero");
+        AtomicEBrake {
+            data: (0..samples).map(|_| AtomicU8::new(EMPTY)).collect(),
+            failures: AtomicUsize::new(0),
+            head: AtomicUsize::new(0),
+            successes: AtomicUsize::new(0),
+            tolerance,
+        }
+    }
+
+    /// Insert a sample into the emergency brake, evicting the oldest sample if the ring is full.
+    pub fn add_sample(&self, sample: bool) {
+        self.store(self.claim_slot(), sample);
+    }
+
+    /// Claims the slot for the next sample, wrapping the head around the ring
+    /// so it never overflows.
+    fn claim_slot(&self) -> usize {
+        let len = self.data.len();
+        match self.head.fetch_update(Ordering::AcqRel, Ordering::Acquire, |head| Some((head + 1) % len)) {
+            Ok(slot) | Err(slot) => slot,
+        }
+    }
+
+    /// Stores a sample in the slot, and subtracts the sample it displaces.
+    fn store(&self, slot: usize, sample: bool) {
+        // Count the new sample before it becomes visible in the ring, so a
+        // writer that evicts it immediately afterwards never underflows.
+        self.counter(sample).fetch_add(1, Ordering::AcqRel);
+        let value = match sample {
+            true => SUCCESS,
+            false => FAILURE,
+        };
+        self.evict(self.data[slot].swap(value, Ordering::AcqRel));
+    }
+
+    /// Subtracts a sample taken out of the ring. An empty slot was never counted.
+    fn evict(&self, value: u8) {
+        match value {
+            SUCCESS => self.successes.fetch_sub(1, Ordering::AcqRel),
+            FAILURE => self.failures.fetch_sub(1, Ordering::AcqRel),
+            _ => return,
+        };
+    }
+
+    /// Returns true if the ring is full and the failures exceed the tolerance.
+    pub fn should_trigger(&self) -> bool {
+        if self.len() < self.data.len() {
+            return false;
+        }
+
+        self.current_failures() > self.tolerance
+    }
+
+    /// Returns `TripOutcome::NotTripped` if the emergency brake has not been triggered.
+    /// If the emergency brake has been triggered, the supplied trigger action will be executed.
+    pub fn trigger(&self, trigger: &Trigger) -> TripOutcome {
+        match self.should_trigger() {
+            true => {
+                error!(failures = self.current_failures(), samples = self.data.len(), "Emergency brake triggered!");
+                trigger.execute()
+            },
+            false => TripOutcome::NotTripped,
+        }
+    }
+
+    /// Insert a sample and check if the emergency brake should be triggered.
+    pub fn trigger_on_sample(&self, sample: bool, trigger: &Trigger) -> TripOutcome {
+        self.add_sample(sample);
+        self.trigger(trigger)
+    }
+
+    /// Clear the ring and all failure and success counts. Each slot is
+    /// emptied and its sample subtracted on its own, so samples recorded
+    /// concurrently may survive the reset, but stay counted.
+    pub fn reset(&self) {
+        self.head.store(0, Ordering::Release);
+        for slot in self.data.iter() {
+            self.evict(slot.swap(EMPTY, Ordering::AcqRel));
+        }
+    }
+
+    /// Returns the number of failures in the ring.
+    pub fn current_failures(&self) -> usize {
+        self.failures.load(Ordering::Acquire)
+    }
+
+    /// Returns the number of successes in the ring.
+    pub fn current_successes(&self) -> usize {
+        self.successes.load(Ordering::Acquire)
+    }
+
+    /// Returns the number of samples currently held in the ring.
+    pub fn len(&self) -> usize {
+        self.current_failures().saturating_add(self.current_successes()).min(self.data.len())
+    }
+
+    /// Returns true if the ring holds no samples.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn counter(&self, sample: bool) -> &AtomicUsize {
+        match sample {
+            true => &self.successes,
+            false => &self.failures,
+        }
+    }
+}
+
+
+/// Test module for the AtomicEBrake.
+#[cfg(test)]
+mod test;
@@ -0,0 +1,105 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+use std::sync::Arc;
+use std::thread;
+
+#[test]
+/// Test that the atomic brake evicts the oldest sample once the ring is full.
+fn it_should_evict_the_oldest_sample() {
+    let ebrake = AtomicEBrake::new(3, 1);
+    ebrake.add_sample(false);
+    ebrake.add_sample(false);
+    assert!(!ebrake.should_trigger());
+
+    ebrake.add_sample(true);
+    assert!(ebrake.should_trigger());
+
+    // Evicts the oldest failure.
+    ebrake.add_sample(true);
+    assert_eq!(ebrake.current_failures(), 1);
+    assert_eq!(ebrake.current_successes(), 2);
+    assert!(!ebrake.should_trigger());
+}
+
+#[test]
+/// Test that resetting clears the ring.
+fn it_should_reset_the_ring() {
+    let ebrake = AtomicEBrake::new(2, 0);
+    ebrake.add_sample(false);
+    ebrake.add_sample(false);
+    assert!(ebrake.trigger(&Trigger::Log).is_tripped());
+
+    ebrake.reset();
+    assert!(ebrake.is_empty());
+    assert_eq!(ebrake.current_failures(), 0);
+    assert!(!ebrake.should_trigger());
+}
+
+#[test]
+/// Stress test that concurrent writers leave the counters consistent with the ring.
+fn it_should_stay_consistent_under_contention() {
+    let ebrake = Arc::new(AtomicEBrake::new(64, 10));
+    let workers: Vec<_> = (0..8)
+        .map(|worker| {
+            let ebrake = Arc::clone(&ebrake);
+            thread::spawn(move || {
+                for i in 0..10_000 {
+                    ebrake.add_sample((i + worker) % 3 != 0);
+                    let _ = ebrake.should_trigger();
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    let failures = ebrake.data.iter().filter(|slot| slot.load(Ordering::Acquire) == FAILURE).count();
+    assert_eq!(ebrake.len(), 64);
+    assert_eq!(ebrake.current_failures(), failures);
+    assert_eq!(ebrake.current_successes(), 64 - failures);
+}
+
+#[test]
+/// Test that a writer that stores into a slot ahead of an earlier writer does not subtract a sample that was never counted.
+fn it_should_count_out_of_order_writers() {
+    let ebrake = AtomicEBrake::new(2, 0);
+    let first = ebrake.claim_slot();
+    let second = ebrake.claim_slot();
+    let wrapped = ebrake.claim_slot();
+    assert_eq!((first, second, wrapped), (0, 1, 0));
+
+    // The writer that wrapped around stores before the first writer.
+    ebrake.store(wrapped, false);
+    assert_eq!((ebrake.current_failures(), ebrake.current_successes()), (1, 0));
+    ebrake.store(first, true);
+    ebrake.store(second, true);
+    assert_eq!((ebrake.current_failures(), ebrake.current_successes()), (0, 2));
+    assert_eq!(ebrake.len(), 2);
+
+    // The same holds right after a reset.
+    ebrake.reset();
+    let first = ebrake.claim_slot();
+    ebrake.claim_slot();
+    let wrapped = ebrake.claim_slot();
+    ebrake.store(wrapped, true);
+    ebrake.store(first, false);
+    assert_eq!((ebrake.current_failures(), ebrake.current_successes()), (1, 0));
+    assert_eq!(ebrake.len(), 1);
+}
+
+#[test]
+/// Test that the head wraps around the ring instead of growing without bound.
+fn it_should_wrap_the_head_around_the_ring() {
+    let ebrake = AtomicEBrake::new(3, 1);
+    for _ in 0..10 {
+        ebrake.add_sample(true);
+        assert!(ebrake.head.load(Ordering::Acquire) < 3);
+    }
+    assert_eq!(ebrake.len(), 3);
+    assert_eq!(ebrake.current_successes(), 3);
+}
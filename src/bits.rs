@@ -0,0 +1,138 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use std::fmt;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// The BitRing is a double-ended queue of booleans packed into 64 bit words.
+/// It stores one bit per sample, using an eighth of the memory of a `VecDeque<bool>`.
+/// The ring grows when a sample is pushed while it is at capacity.
+/// With the `serde` feature, the ring is serialized as a sequence of booleans
+/// from oldest to newest.
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "Vec<bool>", into = "Vec<bool>"))]
+pub(crate) struct BitRing {
+    words: Vec<u64>,
+    head: usize,
+    len: usize,
+}
+
+impl BitRing {
+    /// Creates an empty ring with room for at least `capacity` bits.
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        BitRing {
+            words: vec![0; capacity.div_ceil(WORD_BITS)],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of bits the ring can hold without growing.
+    pub(crate) fn capacity(&self) -> usize {
+        self.words.len() * WORD_BITS
+    }
+
+    /// Returns the number of bits in the ring.
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the ring holds no bits.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes all bits from the ring, keeping its capacity.
+    pub(crate) fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+    }
+
+    /// Returns the bit at the given position, counted from the front.
+    pub(crate) fn get(&self, index: usize) -> Option<bool> {
+        if index >= self.len {
+            return None;
+        }
+
+        let slot = (self.head + index) % self.capacity();
+        Some(self.words[slot / WORD_BITS] & (1 << (slot % WORD_BITS)) != 0)
+    }
+
+    /// Returns the oldest bit.
+    pub(crate) fn front(&self) -> Option<bool> {
+        self.get(0)
+    }
+
+    /// Returns the newest bit.
+    pub(crate) fn back(&self) -> Option<bool> {
+        self.get(self.len.checked_sub(1)?)
+    }
+
+    /// Appends a bit to the back of the ring.
+    pub(crate) fn push_back(&mut self, bit: bool) {
+        if self.len == self.capacity() {
+            self.grow();
+        }
+
+        let slot = (self.head + self.len) % self.capacity();
+        let mask = 1 << (slot % WORD_BITS);
+        match bit {
+            true => self.words[slot / WORD_BITS] |= mask,
+            false => self.words[slot / WORD_BITS] &= !mask,
+        }
+        self.len += 1;
+    }
+
+    /// Removes and returns the oldest bit.
+    pub(crate) fn pop_front(&mut self) -> Option<bool> {
+        let bit = self.front()?;
+        self.head = (self.head + 1) % self.capacity();
+        self.len -= 1;
+        Some(bit)
+    }
+
+    /// Iterates over the bits from oldest to newest.
+    pub(crate) fn iter(&self) -> impl DoubleEndedIterator<Item = bool> + ExactSizeIterator + '_ {
+        (0..self.len).map(move |index| self.get(index).unwrap_or_default())
+    }
+
+    /// Doubles the capacity of the ring, moving the bits so the front is at slot zero.
+    fn grow(&mut self) {
+        let mut grown = BitRing::with_capacity((self.capacity() * 2).max(WORD_BITS));
+        for bit in self.iter() {
+            grown.push_back(bit);
+        }
+        *self = grown;
+    }
+}
+
+impl From<Vec<bool>> for BitRing {
+    fn from(bits: Vec<bool>) -> Self {
+        let mut ring = BitRing::with_capacity(bits.len());
+        for bit in bits {
+            ring.push_back(bit);
+        }
+        ring
+    }
+}
+
+impl From<BitRing> for Vec<bool> {
+    fn from(ring: BitRing) -> Self {
+        ring.iter().collect()
+    }
+}
+
+impl fmt::Debug for BitRing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+
+/// Test module for the BitRing.
+#[cfg(test)]
+mod test;
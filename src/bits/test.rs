@@ -0,0 +1,67 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+use crate::{EBrake, EmergencyBrake};
+use crate::test_util::random_samples;
+use std::collections::VecDeque;
+
+#[test]
+/// Test that the bit ring behaves like a VecDeque of booleans across wraparound.
+fn it_should_match_a_vec_deque() {
+    let mut ring = BitRing::with_capacity(100);
+    let mut deque = VecDeque::with_capacity(100);
+    for sample in random_samples(10_000, 0x9E37_79B9_7F4A_7C15) {
+        if deque.len() == 100 {
+            assert_eq!(ring.pop_front(), deque.pop_front());
+        }
+        ring.push_back(sample);
+        deque.push_back(sample);
+        assert_eq!(ring.len(), deque.len());
+        assert_eq!(ring.front(), deque.front().copied());
+        assert_eq!(ring.back(), deque.back().copied());
+    }
+    assert!(ring.iter().eq(deque.iter().copied()));
+}
+
+#[test]
+/// Test that pushing into a full ring grows it without losing bits.
+fn it_should_grow_when_full() {
+    let mut ring = BitRing::with_capacity(0);
+    let samples: Vec<bool> = random_samples(200, 42).collect();
+    for sample in &samples {
+        ring.push_back(*sample);
+    }
+    assert_eq!(ring.len(), 200);
+    assert!(ring.capacity() >= 200);
+    assert!(ring.iter().eq(samples.iter().copied()));
+}
+
+#[test]
+/// Test that a large window uses one bit per sample.
+fn it_should_pack_samples_into_words() {
+    let ring = BitRing::with_capacity(100_000);
+    assert_eq!(ring.words.len(), 1563);
+}
+
+#[test]
+/// Test that the brake produces the same decisions as a VecDeque-backed reference.
+fn it_should_trigger_like_the_reference_implementation() {
+    let (samples, tolerance) = (64, 20);
+    let mut ebrake = EBrake::new(samples, tolerance);
+    let mut reference: VecDeque<bool> = VecDeque::new();
+    for sample in random_samples(5_000, 7) {
+        if reference.len() == samples {
+            reference.pop_front();
+        }
+        reference.push_back(sample);
+        ebrake.add_sample(sample);
+
+        let failures = reference.iter().filter(|sample| !**sample).count();
+        assert_eq!(ebrake.current_failures(), failures);
+        assert_eq!(ebrake.current_successes(), reference.len() - failures);
+        assert_eq!(ebrake.should_trigger(), reference.len() == samples && failures > tolerance);
+    }
+}
@@ -0,0 +1,86 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+impl EBrake {
+    /// Sets the blocking HTTP client used for blocking service checks.
+    #[cfg_attr(docsrs, doc(cfg(feature = "service_checker_blocking")))]
+    pub fn set_blocking_http_client(&mut self, client: reqwest::blocking::Client) {
+        self.blocking_client = std::sync::OnceLock::from(client);
+    }
+
+    /// Returns the blocking HTTP client used for blocking service checks,
+    /// creating a default client on first use if none has been set.
+    #[cfg_attr(docsrs, doc(cfg(feature = "service_checker_blocking")))]
+    pub fn blocking_http_client(&self) -> &reqwest::blocking::Client {
+        self.blocking_client.get_or_init(reqwest::blocking::Client::new)
+    }
+
+    /// Check if the service is running without an async runtime. This takes a
+    /// URI as a parameter, and performs a basic HTTP GET request to the URI,
+    /// blocking the current thread until it completes. If the request succeeds
+    /// with a 2xx status, it will return true, false otherwise. The request
+    /// times out after `DEFAULT_CHECK_TIMEOUT`.
+    ///
+    /// This must not be called from within an async runtime.
+    #[cfg_attr(docsrs, doc(cfg(feature = "service_checker_blocking")))]
+    pub fn check_service_endpoint_blocking(&self, uri: &str) -> bool {
+        self.check_service_endpoint_blocking_with(uri, &ServiceCheckConfig::default())
+    }
+
+    /// Similar to check_service_endpoint_blocking, but uses the supplied
+    /// configuration for the HTTP method, request headers, acceptable
+    /// statuses, body matcher and timeout.
+    #[cfg_attr(docsrs, doc(cfg(feature = "service_checker_blocking")))]
+    pub fn check_service_endpoint_blocking_with(&self, uri: &str, config: &ServiceCheckConfig) -> bool {
+        self.classify_service_endpoint_blocking(uri, config).is_none()
+    }
+
+    /// Similar to check_service_endpoint_blocking_with, but returns the
+    /// category of a failed check, or `None` if the service is running, like
+    /// `classify_service_endpoint`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "service_checker_blocking")))]
+    pub fn classify_service_endpoint_blocking(&self, uri: &str, config: &ServiceCheckConfig) -> Option<ErrorKind> {
+        self.try_classify_service_endpoint_blocking(uri, config).unwrap_or(Some(ErrorKind::Other))
+    }
+
+    /// Similar to classify_service_endpoint_blocking, but returns an error if
+    /// the check could not be made because of the local configuration, like
+    /// `try_classify_service_endpoint`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "service_checker_blocking")))]
+    pub fn try_classify_service_endpoint_blocking(&self, uri: &str, config: &ServiceCheckConfig) -> Result<Option<ErrorKind>, ServiceCheckError> {
+        let response = config
+            .blocking_client(self.blocking_http_client())?
+            .request(config.method.clone(), uri)
+            .headers(config.headers.clone())
+            .timeout(config.timeout)
+            .send();
+        let response = match response {
+            Ok(response) if config.accepts_redirect(response.status()) => return Ok(None),
+            Ok(response) if config.accepts(response.status()) => response,
+            Ok(response) => return Ok(Some(check_config::status_error_kind(response.status()))),
+            Err(error) => return check_config::request_failure(error).map(Some),
+        };
+        match &config.body_matcher {
+            Some(matcher) => match response.text() {
+                Ok(body) => Ok((!matcher.matches(&body)).then_some(ErrorKind::ServerError)),
+                Err(error) => Ok(Some(check_config::request_error_kind(&error))),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Similar to check_service_endpoint_blocking_with, but returns an error
+    /// if the check could not be made because of the local configuration.
+    #[cfg_attr(docsrs, doc(cfg(feature = "service_checker_blocking")))]
+    pub fn try_check_service_endpoint_blocking(&self, uri: &str, config: &ServiceCheckConfig) -> Result<bool, ServiceCheckError> {
+        Ok(self.try_classify_service_endpoint_blocking(uri, config)?.is_none())
+    }
+}
+
+/// Test module for the blocking service checks.
+#[cfg(test)]
+mod test;
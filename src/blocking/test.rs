@@ -0,0 +1,138 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread;
+use std::time::Duration;
+
+/// Serves the given statuses, one per connection, from a background thread.
+/// Returns the URI of the server.
+fn serve(statuses: &[(u16, Duration)]) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let uri = format!("http://{}/", listener.local_addr().unwrap());
+    let statuses = statuses.to_vec();
+    thread::spawn(move || {
+        for (status, delay) in statuses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                match stream.read(&mut buffer) {
+                    Ok(0) | Err(_) => break,
+                    Ok(read) => request.extend_from_slice(&buffer[..read]),
+                }
+            }
+            thread::sleep(delay);
+            let reply = format!("HTTP/1.1 {} Mock\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+            let _ = stream.write_all(reply.as_bytes());
+        }
+    });
+    uri
+}
+
+#[test]
+/// Test that a responding endpoint is reported as running.
+fn it_should_check_a_running_endpoint() {
+    let uri = serve(&[(200, Duration::ZERO)]);
+    assert!(EBrake::new(10, 3).check_service_endpoint_blocking(&uri));
+}
+
+#[test]
+/// Test that an unsuccessful status is reported as a failure.
+fn it_should_require_a_successful_status() {
+    let uri = serve(&[(503, Duration::ZERO)]);
+    assert!(!EBrake::new(10, 3).check_service_endpoint_blocking(&uri));
+}
+
+#[test]
+/// Test that the configured statuses and timeout are applied like the async check.
+fn it_should_apply_the_check_configuration() {
+    let uri = serve(&[(404, Duration::ZERO), (200, Duration::from_millis(500))]);
+    let config = ServiceCheckConfig {
+        acceptable_statuses: Some(vec![404]),
+        timeout: Duration::from_millis(100),
+        ..ServiceCheckConfig::default()
+    };
+    let ebrake = EBrake::new(10, 3);
+    assert!(ebrake.check_service_endpoint_blocking_with(&uri, &config));
+    assert!(!ebrake.check_service_endpoint_blocking_with(&uri, &config));
+}
+
+#[test]
+#[cfg(feature = "service_checker")]
+/// Test that the blocking check agrees with the async check.
+fn it_should_match_the_async_check() {
+    let responses = [(200, Duration::ZERO), (500, Duration::ZERO), (204, Duration::ZERO), (302, Duration::ZERO)];
+    // Each response is served twice: once to each check.
+    let uri = serve(&responses.iter().flat_map(|response| [*response, *response]).collect::<Vec<_>>());
+    let ebrake = EBrake::new(10, 3);
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    for _ in responses {
+        let expected = runtime.block_on(ServiceChecker::check_service_endpoint(&ebrake, &uri));
+        assert_eq!(ebrake.check_service_endpoint_blocking(&uri), expected);
+    }
+}
+
+#[test]
+/// Test that a 302 is followed by default and checked itself when redirects are not followed.
+fn it_should_apply_the_redirect_policy() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let uri = format!("http://{}/health", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                match stream.read(&mut buffer) {
+                    Ok(0) | Err(_) => break,
+                    Ok(read) => request.extend_from_slice(&buffer[..read]),
+                }
+            }
+            let reply = match request.starts_with(b"GET /health ") {
+                true => "HTTP/1.1 302 Mock\r\nLocation: /login\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                false => "HTTP/1.1 200 Mock\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            };
+            let _ = stream.write_all(reply.as_bytes());
+        }
+    });
+
+    let ebrake = EBrake::new(10, 3);
+    assert!(ebrake.check_service_endpoint_blocking(&uri));
+    let mut config = ServiceCheckConfig {
+        redirect: RedirectPolicy::None,
+        ..ServiceCheckConfig::default()
+    };
+    assert!(!ebrake.check_service_endpoint_blocking_with(&uri, &config));
+    config.redirect_is_failure = false;
+    assert!(ebrake.check_service_endpoint_blocking_with(&uri, &config));
+}
+
+#[test]
+/// Test that the blocking check classifies an unresolvable host name as a DNS failure.
+fn it_should_classify_an_unresolvable_host_as_dns() {
+    let ebrake = EBrake::new(10, 3);
+    let config = ServiceCheckConfig::default();
+    assert_eq!(ebrake.classify_service_endpoint_blocking("http://emergency-brake.invalid/", &config), Some(ErrorKind::Dns));
+
+    let uri = serve(&[(503, Duration::ZERO)]);
+    assert_eq!(ebrake.classify_service_endpoint_blocking(&uri, &config), Some(ErrorKind::ServerError));
+}
+
+#[test]
+/// Test that the blocking check reports a client that cannot be built apart from a failed check.
+fn it_should_report_a_client_build_error() {
+    let ebrake = EBrake::new(10, 3);
+    let config = ServiceCheckConfig {
+        proxy: Some("not a proxy url".to_string()),
+        ..ServiceCheckConfig::default()
+    };
+    let uri = serve(&[(200, Duration::ZERO)]);
+    assert!(matches!(ebrake.try_check_service_endpoint_blocking(&uri, &config), Err(ServiceCheckError::Client(_))));
+    assert!(!ebrake.check_service_endpoint_blocking_with(&uri, &config));
+    assert!(ebrake.try_check_service_endpoint_blocking(&uri, &ServiceCheckConfig::default()).unwrap());
+}
@@ -0,0 +1,153 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+/// The EBrakeBuilder constructs an EBrake with named, self-documenting options.
+/// ```
+/// use emergency_brake::*;
+/// let ebrake = EBrakeBuilder::new()
+///     .window_size(25)
+///     .failure_threshold(3)
+///     .default_trigger(Trigger::Abort)
+///     .build()
+///     .unwrap();
+/// assert_eq!(ebrake.trigger_default(), TripOutcome::NotTripped);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct EBrakeBuilder {
+    window_size: usize,
+    failure_threshold: usize,
+    failure_rate: Option<f64>,
+    reset_failure_rate: Option<f64>,
+    default_trigger: Trigger,
+    clock: Option<SharedClock>,
+    cooldown: Option<Duration>,
+    half_open_max_probes: Option<usize>,
+    #[cfg(feature = "service_checker")]
+    http_client: Option<reqwest::Client>,
+    latching: bool,
+    log_level: Option<tracing::Level>,
+    name: Option<String>,
+}
+
+impl EBrakeBuilder {
+    /// Creates a new builder. The window size must be set before building.
+    pub fn new() -> Self {
+        EBrakeBuilder::default()
+    }
+
+    /// Sets the number of samples held in the sample window.
+    pub fn window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    /// Sets the number of failures that may be present in the window before
+    /// the emergency brake is triggered.
+    pub fn failure_threshold(mut self, failure_threshold: usize) -> Self {
+        self.failure_threshold = failure_threshold;
+        self
+    }
+
+    /// Makes the brake trip on the fraction of failures in the window rather
+    /// than their count. See `EBrake::new_with_rate`.
+    pub fn failure_rate(mut self, failure_rate: f64) -> Self {
+        self.failure_rate = Some(failure_rate);
+        self
+    }
+
+    /// Sets the failure rate below which a tripped brake resets.
+    /// See `EBrake::set_reset_failure_rate`.
+    pub fn reset_failure_rate(mut self, reset_failure_rate: f64) -> Self {
+        self.reset_failure_rate = Some(reset_failure_rate);
+        self
+    }
+
+    /// Sets the trigger action used by `trigger_default`.
+    pub fn default_trigger(mut self, trigger: Trigger) -> Self {
+        self.default_trigger = trigger;
+        self
+    }
+
+    /// Sets the clock used for time-based behavior such as the cooldown.
+    /// Defaults to the system clock.
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Some(SharedClock::new(clock));
+        self
+    }
+
+    /// Enables circuit breaker recovery with the given cooldown.
+    /// See `EBrake::set_cooldown`.
+    pub fn cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = Some(cooldown);
+        self
+    }
+
+    /// Sets how many probe samples a half-open brake admits before deciding.
+    /// See `EBrake::set_half_open_max_probes`.
+    pub fn half_open_max_probes(mut self, max_probes: usize) -> Self {
+        self.half_open_max_probes = Some(max_probes);
+        self
+    }
+
+    /// Sets the HTTP client used for service checks.
+    #[cfg_attr(docsrs, doc(cfg(feature = "service_checker")))]
+    #[cfg(feature = "service_checker")]
+    pub fn http_client(mut self, client: reqwest::Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Sets the name included in the brake's trip events.
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Makes the built brake stay tripped after its first trip until it is reset.
+    pub fn latching(mut self, latching: bool) -> Self {
+        self.latching = latching;
+        self
+    }
+
+    /// Sets the level at which trip events are logged.
+    pub fn log_level(mut self, level: tracing::Level) -> Self {
+        self.log_level = Some(level);
+        self
+    }
+
+    /// Builds the EBrake, validating the configuration.
+    pub fn build(self) -> Result<EBrake, EBrakeError> {
+        let mut ebrake = match self.failure_rate {
+            Some(failure_rate) => EBrake::new_with_rate(self.window_size, failure_rate)?,
+            None => EBrake::try_new(self.window_size, self.failure_threshold)?,
+        };
+        if let Some(reset_failure_rate) = self.reset_failure_rate {
+            ebrake.set_reset_failure_rate(reset_failure_rate)?;
+        }
+        ebrake.default_trigger = self.default_trigger;
+        if let Some(clock) = self.clock {
+            ebrake.clock = clock;
+        }
+        ebrake.cooldown = self.cooldown;
+        if let Some(max_probes) = self.half_open_max_probes {
+            ebrake.set_half_open_max_probes(max_probes);
+        }
+        ebrake.latching = self.latching;
+        ebrake.log_level = self.log_level;
+        ebrake.name = self.name;
+        #[cfg(feature = "service_checker")]
+        if let Some(client) = self.http_client {
+            ebrake.set_http_client(client);
+        }
+        Ok(ebrake)
+    }
+}
+
+
+/// Test module for the EBrakeBuilder.
+#[cfg(test)]
+mod test;
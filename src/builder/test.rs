@@ -0,0 +1,91 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+#[test]
+/// Test that the builder produces a brake with the configured values.
+fn it_should_build_with_configured_values() {
+    let ebrake = EBrake::builder()
+        .window_size(20)
+        .failure_threshold(4)
+        .default_trigger(Trigger::Abort)
+        .build()
+        .unwrap();
+    assert_eq!(ebrake.samples, 20);
+    assert_eq!(ebrake.tolerance, 4);
+    assert_eq!(ebrake.default_trigger, Trigger::Abort);
+    assert!(!ebrake.trigger_default().is_tripped());
+}
+
+#[test]
+/// Test that the builder rejects a missing window size.
+fn it_should_reject_a_missing_window_size() {
+    let result = EBrakeBuilder::new().failure_threshold(3).build();
+    assert_eq!(result.unwrap_err(), EBrakeError::ZeroWindow);
+}
+
+#[test]
+/// Test that the builder rejects a threshold that can never be exceeded.
+fn it_should_reject_an_unreachable_threshold() {
+    let result = EBrakeBuilder::new().window_size(3).failure_threshold(3).build();
+    assert_eq!(result.unwrap_err(), EBrakeError::ToleranceTooLarge);
+}
+
+#[test]
+#[should_panic(expected = "Emergency brake triggered!")]
+/// Test that trigger_default uses the configured default trigger.
+fn it_should_fire_the_default_trigger() {
+    let mut ebrake = EBrake::builder()
+        .window_size(5)
+        .failure_threshold(1)
+        .default_trigger(Trigger::Panic)
+        .build()
+        .unwrap();
+    for _ in 0..5 {
+        ebrake.add_sample(false);
+    }
+    ebrake.trigger_default();
+}
+
+#[test]
+/// Test that the default trigger methods honor a configured Log trigger.
+fn it_should_honor_a_default_log_trigger() {
+    let mut ebrake = EBrake::builder()
+        .window_size(3)
+        .failure_threshold(1)
+        .default_trigger(Trigger::Log)
+        .build()
+        .unwrap();
+    assert_eq!(ebrake.default_trigger(), &Trigger::Log);
+    assert_eq!(ebrake.trigger_default(), TripOutcome::NotTripped);
+    assert_eq!(ebrake.trigger_on_sample_default(false), TripOutcome::NotTripped);
+    assert_eq!(ebrake.trigger_on_sample_default(false), TripOutcome::NotTripped);
+    assert_eq!(ebrake.trigger_on_sample_default(true), TripOutcome::Handled);
+    assert_eq!(ebrake.trigger_default(), TripOutcome::Handled);
+}
+
+#[test]
+/// Test that a configured mock clock drives the cooldown without real sleeps.
+fn it_should_drive_the_cooldown_with_a_mock_clock() {
+    let clock = MockClock::new();
+    let mut ebrake = EBrake::builder()
+        .window_size(2)
+        .failure_threshold(0)
+        .clock(clock.clone())
+        .cooldown(Duration::from_secs(60))
+        .build()
+        .unwrap();
+    ebrake.extend([false, false]);
+    assert!(matches!(ebrake.state(), BrakeState::Open { .. }));
+
+    clock.advance(Duration::from_secs(59));
+    assert!(matches!(ebrake.state(), BrakeState::Open { .. }));
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(ebrake.state(), BrakeState::HalfOpen);
+
+    ebrake.add_sample(true);
+    assert_eq!(ebrake.state(), BrakeState::Closed);
+}
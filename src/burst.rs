@@ -0,0 +1,101 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+/// The BurstSampler counts outcomes in a hot loop without touching the brake,
+/// then folds them into the window in one `EBrake::add_burst` call on `flush`.
+/// Recording an outcome is a single counter increment, so the per-sample
+/// tracing and metrics overhead is only paid once per window on each flush.
+/// ```
+/// use emergency_brake::*;
+/// let mut ebrake = EBrake::new(10, 3);
+/// let mut sampler = BurstSampler::new();
+/// for i in 0..1_000_000 {
+///     sampler.record(i % 100 != 0);
+/// }
+/// sampler.flush(&mut ebrake);
+/// assert_eq!(ebrake.current_failures(), 10);
+/// assert_eq!(sampler.pending(), 0);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BurstSampler {
+    failures: usize,
+    successes: usize,
+}
+
+impl BurstSampler {
+    /// Creates a new sampler with no pending outcomes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts an outcome until the next flush.
+    pub fn record(&mut self, sample: bool) {
+        match sample {
+            true => self.successes = self.successes.saturating_add(1),
+            false => self.failures = self.failures.saturating_add(1),
+        }
+    }
+
+    /// Returns the number of outcomes recorded since the last flush.
+    pub fn pending(&self) -> usize {
+        self.successes.saturating_add(self.failures)
+    }
+
+    /// Folds the pending outcomes into the emergency brake and clears them.
+    pub fn flush(&mut self, ebrake: &mut EBrake) {
+        ebrake.add_burst(self.successes, self.failures);
+        *self = Self::default();
+    }
+}
+
+impl EBrake {
+    /// Inserts a burst of outcomes at once. The failures are treated as the
+    /// newest samples, so the result matches adding the successes and then the
+    /// failures one at a time. A burst larger than the window collapses to the
+    /// final window-sized state without visiting the discarded samples, so a
+    /// trip that would have started and ended within them is not counted.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(5, 2);
+    /// ebrake.add_burst(1_000, 3);
+    /// assert_eq!(ebrake.current_successes(), 2);
+    /// assert_eq!(ebrake.current_failures(), 3);
+    /// assert_eq!(ebrake.total_samples(), 1_003);
+    /// assert!(ebrake.should_trigger());
+    /// ```
+    pub fn add_burst(&mut self, successes: usize, failures: usize) {
+        let total = successes.saturating_add(failures);
+        if self.samples == 0 {
+            self.total_samples = self.total_samples.saturating_add(total as u64);
+            return;
+        }
+
+        let skipped = total.saturating_sub(self.samples);
+        let skipped_successes = successes.min(skipped);
+        let skipped_failures = skipped - skipped_successes;
+        if let Some(adaptive) = &mut self.adaptive {
+            adaptive.observe_many(true, skipped_successes);
+            adaptive.observe_many(false, skipped_failures);
+        }
+        // Skipped samples still extend or break the failure streak.
+        match skipped_successes {
+            0 => self.failure_streak = self.failure_streak.saturating_add(skipped_failures),
+            _ => self.failure_streak = skipped_failures,
+        }
+        self.total_samples = self.total_samples.saturating_add(skipped as u64);
+        for _ in skipped_successes..successes {
+            self.add_sample(true);
+        }
+        for _ in skipped_failures..failures {
+            self.add_sample(false);
+        }
+    }
+}
+
+/// Test module for burst sampling.
+#[cfg(test)]
+mod test;
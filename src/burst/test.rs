@@ -0,0 +1,68 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+/// Asserts that a burst leaves the brake in the same state as adding the
+/// successes and then the failures one at a time. Trips within discarded
+/// samples are not counted by a burst, so only the lifetime trip count may differ.
+fn assert_burst_matches(samples: usize, tolerance: usize, bursts: &[(usize, usize)]) {
+    let mut burst = EBrake::new(samples, tolerance);
+    let mut single = EBrake::new(samples, tolerance);
+    for &(successes, failures) in bursts {
+        burst.add_burst(successes, failures);
+        (0..successes).for_each(|_| single.add_sample(true));
+        (0..failures).for_each(|_| single.add_sample(false));
+        assert_eq!(burst.to_vec(), single.to_vec());
+        let snapshot = BrakeSnapshot { total_trips: single.total_trips(), ..burst.snapshot() };
+        assert_eq!(snapshot, single.snapshot());
+        assert!(burst.total_trips() <= single.total_trips());
+        assert_eq!(burst.current_streak(), single.current_streak());
+        assert_eq!(burst.should_trigger(), single.should_trigger());
+    }
+}
+
+#[test]
+/// Test that bursts match per-sample insertion, including bursts larger than the window.
+fn it_should_match_per_sample_insertion() {
+    assert_burst_matches(5, 2, &[(0, 0), (1, 1), (3, 0), (0, 2), (10, 3), (2, 8), (0, 0), (5, 5)]);
+    assert_burst_matches(1, 0, &[(4, 0), (0, 4), (3, 1), (1, 0)]);
+    assert_burst_matches(25, 3, &[(100, 0), (0, 4), (20, 30), (7, 0), (1000, 1000)]);
+}
+
+#[test]
+/// Test that a failure streak spanning several bursts is preserved.
+fn it_should_extend_the_streak_across_bursts() {
+    let mut ebrake = EBrake::new(3, 2);
+    ebrake.add_burst(0, 2);
+    ebrake.add_burst(0, 10);
+    assert_eq!(ebrake.current_streak(), 12);
+    ebrake.add_burst(1, 0);
+    assert_eq!(ebrake.current_streak(), 0);
+}
+
+#[test]
+/// Test that a zero-size window only counts the burst towards the totals.
+fn it_should_count_a_burst_on_a_zero_size_window() {
+    let mut ebrake = EBrake::new(0, 0);
+    ebrake.add_burst(3, 4);
+    assert!(ebrake.is_empty());
+    assert_eq!(ebrake.total_samples(), 7);
+}
+
+#[test]
+/// Test that the sampler accumulates outcomes until it is flushed.
+fn it_should_accumulate_outcomes_until_flushed() {
+    let mut ebrake = EBrake::new(4, 1);
+    let mut sampler = BurstSampler::new();
+    [true, false, false, true, false].iter().for_each(|sample| sampler.record(*sample));
+    assert_eq!(sampler.pending(), 5);
+    assert!(ebrake.is_empty());
+
+    sampler.flush(&mut ebrake);
+    assert_eq!(sampler, BurstSampler::new());
+    assert_eq!(ebrake.to_vec(), vec![true, false, false, false]);
+    assert!(ebrake.should_trigger());
+}
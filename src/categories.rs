@@ -0,0 +1,116 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+use std::collections::HashMap;
+
+/// The ErrorKind enum categorizes a failure, so the failures in a window can
+/// be broken down by cause.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ErrorKind {
+    /// The operation did not complete in time.
+    Timeout,
+
+    /// A connection could not be established or was reset.
+    Connection,
+
+    /// The dependency's host name could not be resolved.
+    Dns,
+
+    /// The dependency rejected the request, such as with a 4xx status.
+    ClientError,
+
+    /// The dependency failed to handle the request, such as with a 5xx status.
+    ServerError,
+
+    /// Any other failure.
+    Other,
+}
+
+/// The categories of the samples in a window, kept alongside the window and
+/// evicted with it. Successes and uncategorized failures have no category.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct CategoryWindow {
+    counts: HashMap<ErrorKind, usize>,
+    kinds: VecDeque<Option<ErrorKind>>,
+}
+
+impl CategoryWindow {
+    /// Creates a window for `len` samples recorded before categories were tracked.
+    pub(crate) fn with_len(len: usize) -> Self {
+        CategoryWindow {
+            counts: HashMap::new(),
+            kinds: VecDeque::from(vec![None; len]),
+        }
+    }
+
+    pub(crate) fn push_back(&mut self, kind: Option<ErrorKind>) {
+        if let Some(kind) = kind {
+            *self.counts.entry(kind).or_default() += 1;
+        }
+        self.kinds.push_back(kind);
+    }
+
+    /// Returns the category of each sample, oldest first.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = Option<ErrorKind>> + '_ {
+        self.kinds.iter().copied()
+    }
+
+    /// Returns true if there is a category for every sample in the window,
+    /// only failures are categorized, and the counts match the categories.
+    #[cfg(feature = "serde")]
+    pub(crate) fn matches_window(&self, data: &BitRing) -> bool {
+        let mut counts = HashMap::new();
+        for (sample, kind) in data.iter().zip(&self.kinds) {
+            match (sample, kind) {
+                (true, Some(_)) => return false,
+                (false, Some(kind)) => *counts.entry(*kind).or_insert(0) += 1,
+                (_, None) => {},
+            }
+        }
+        self.kinds.len() == data.len() && counts == self.counts
+    }
+
+    pub(crate) fn pop_front(&mut self) {
+        if let Some(Some(kind)) = self.kinds.pop_front() {
+            if let Some(count) = self.counts.get_mut(&kind) {
+                *count = count.saturating_sub(1);
+                if *count == 0 {
+                    self.counts.remove(&kind);
+                }
+            }
+        }
+    }
+}
+
+impl EBrake {
+    /// Insert a sample into the emergency brake with the category of its
+    /// failure, where `None` is a success. The brake still trips on the total
+    /// number of failures, regardless of their categories.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(10, 3);
+    /// ebrake.add_categorized_sample(Some(ErrorKind::Timeout));
+    /// ebrake.add_categorized_sample(None);
+    /// ebrake.add_categorized_sample(Some(ErrorKind::Timeout));
+    /// assert_eq!(ebrake.category_counts()[&ErrorKind::Timeout], 2);
+    /// ```
+    pub fn add_categorized_sample(&mut self, category: Option<ErrorKind>) {
+        self.push_sample(category.is_none(), 1, category);
+    }
+
+    /// Returns the number of failures of each category currently in the window.
+    /// Categories without failures in the window are omitted.
+    pub fn category_counts(&self) -> HashMap<ErrorKind, usize> {
+        self.categories.as_ref().map(|categories| categories.counts.clone()).unwrap_or_default()
+    }
+}
+
+/// Test module for the failure categories.
+#[cfg(test)]
+mod test;
@@ -0,0 +1,58 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+#[test]
+/// Test that the category counts follow the window across rollover.
+fn it_should_count_categories_across_rollover() {
+    let mut ebrake = EBrake::new(4, 2);
+    for category in [
+        Some(ErrorKind::Timeout),
+        Some(ErrorKind::ServerError),
+        None,
+        Some(ErrorKind::Timeout),
+        Some(ErrorKind::Connection),
+    ] {
+        ebrake.add_categorized_sample(category);
+    }
+
+    let counts = ebrake.category_counts();
+    assert_eq!(counts.get(&ErrorKind::Timeout), Some(&1));
+    assert_eq!(counts.get(&ErrorKind::ServerError), Some(&1));
+    assert_eq!(counts.get(&ErrorKind::Connection), Some(&1));
+    assert_eq!(ebrake.current_failures(), 3);
+    assert!(ebrake.should_trigger());
+
+    ebrake.extend([true, true]);
+    let counts = ebrake.category_counts();
+    assert_eq!(counts.get(&ErrorKind::ServerError), None);
+    assert_eq!(counts.len(), 2);
+}
+
+#[test]
+/// Test that plain samples recorded before and after categorized ones are uncategorized.
+fn it_should_leave_plain_failures_uncategorized() {
+    let mut ebrake = EBrake::new(3, 2);
+    ebrake.add_sample(false);
+    ebrake.add_categorized_sample(Some(ErrorKind::Other));
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.current_failures(), 3);
+    assert_eq!(ebrake.category_counts().values().sum::<usize>(), 1);
+
+    ebrake.add_sample(true);
+    assert_eq!(ebrake.category_counts().get(&ErrorKind::Other), Some(&1));
+    ebrake.add_sample(true);
+    assert!(ebrake.category_counts().is_empty());
+}
+
+#[test]
+/// Test that reset clears the category counts.
+fn it_should_reset_the_category_counts() {
+    let mut ebrake = EBrake::new(3, 1);
+    ebrake.add_categorized_sample(Some(ErrorKind::ClientError));
+    ebrake.reset();
+    assert!(ebrake.category_counts().is_empty());
+}
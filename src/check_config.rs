@@ -0,0 +1,269 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use crate::ErrorKind;
+use reqwest::header::HeaderMap;
+use reqwest::Method;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The default time allowed for a service check before it is treated as a failure.
+pub const DEFAULT_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The ServiceCheckConfig configures how a service endpoint is checked.
+#[cfg_attr(docsrs, doc(cfg(any(feature = "service_checker", feature = "service_checker_blocking"))))]
+#[derive(Clone, Debug)]
+pub struct ServiceCheckConfig {
+    /// The HTTP method used for the request, such as `HEAD` for a lightweight probe.
+    pub method: Method,
+
+    /// Additional headers sent with the request, such as an authorization token.
+    pub headers: HeaderMap,
+
+    /// The time allowed for the request to complete.
+    pub timeout: Duration,
+
+    /// The status codes that indicate a running service.
+    /// When `None`, any 2xx status is accepted.
+    pub acceptable_statuses: Option<Vec<u16>>,
+
+    /// Checks the response body of an acceptable status, for services that
+    /// report a degraded state with a successful status. A body that does
+    /// not match is treated as a failure. When `None`, the body is not read.
+    pub body_matcher: Option<BodyMatcher>,
+
+    /// Whether redirects are followed, so a check can be pinned to the exact
+    /// endpoint rather than the page it redirects to.
+    pub redirect: RedirectPolicy,
+
+    /// Whether a redirect that is not followed counts as a failure. When false,
+    /// an unfollowed redirect is treated as a running service.
+    pub redirect_is_failure: bool,
+
+    /// Whether connections are kept alive and reused across checks. When false,
+    /// every check opens a new connection, so each one exercises the full
+    /// connection setup.
+    pub keep_alive: bool,
+
+    /// The URL of a proxy that every check request is sent through. A proxy
+    /// requires a dedicated HTTP client, so the client set with
+    /// `set_http_client` is not used by such checks.
+    pub proxy: Option<String>,
+}
+
+/// The ServiceCheckError describes a check that could not be made because of
+/// the local configuration, as opposed to a service that is not running. Such
+/// an error is not evidence about the service, so it should not be recorded
+/// as a failure.
+#[cfg_attr(docsrs, doc(cfg(any(feature = "service_checker", feature = "service_checker_blocking"))))]
+#[derive(Debug)]
+pub enum ServiceCheckError {
+    /// The HTTP client could not be built from the configuration, such as
+    /// with an invalid proxy URL or TLS settings.
+    Client(reqwest::Error),
+
+    /// The request could not be built, such as with an invalid URI.
+    Request(reqwest::Error),
+}
+
+impl fmt::Display for ServiceCheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceCheckError::Client(error) => write!(f, "failed to build the HTTP client: {}", error),
+            ServiceCheckError::Request(error) => write!(f, "failed to build the check request: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for ServiceCheckError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ServiceCheckError::Client(error) | ServiceCheckError::Request(error) => Some(error),
+        }
+    }
+}
+
+/// The RedirectPolicy enum describes how a service check handles redirects.
+/// A policy other than `Follow`, or disabling keep-alive, requires a
+/// dedicated HTTP client, so the client set with `set_http_client` is not
+/// used by such checks.
+#[cfg_attr(docsrs, doc(cfg(any(feature = "service_checker", feature = "service_checker_blocking"))))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RedirectPolicy {
+    /// Follow redirects with the HTTP client's default policy.
+    #[default]
+    Follow,
+
+    /// Do not follow redirects. The redirect response itself is checked.
+    None,
+
+    /// Follow up to the given number of redirects. A check that would need
+    /// more is treated as a failure.
+    Limit(usize),
+}
+
+/// The BodyMatcher is a predicate over the body of a health check response.
+/// ```
+/// use emergency_brake::*;
+/// let config = ServiceCheckConfig {
+///     body_matcher: Some(BodyMatcher::contains(r#""status":"ok""#)),
+///     ..ServiceCheckConfig::default()
+/// };
+/// ```
+#[cfg_attr(docsrs, doc(cfg(any(feature = "service_checker", feature = "service_checker_blocking"))))]
+#[derive(Clone)]
+pub struct BodyMatcher(Arc<dyn Fn(&str) -> bool + Send + Sync>);
+
+impl BodyMatcher {
+    /// Creates a matcher from a predicate that returns true for a healthy body.
+    pub fn new(matcher: impl Fn(&str) -> bool + Send + Sync + 'static) -> Self {
+        BodyMatcher(Arc::new(matcher))
+    }
+
+    /// Creates a matcher that requires the body to contain the given text.
+    pub fn contains(text: &str) -> Self {
+        let text = text.to_string();
+        BodyMatcher::new(move |body| body.contains(&text))
+    }
+
+    /// Returns true if the body indicates a healthy service.
+    pub fn matches(&self, body: &str) -> bool {
+        (self.0)(body)
+    }
+}
+
+impl fmt::Debug for BodyMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BodyMatcher")
+    }
+}
+
+impl ServiceCheckConfig {
+    /// Returns true if the status code indicates a running service.
+    pub(crate) fn accepts(&self, status: reqwest::StatusCode) -> bool {
+        match &self.acceptable_statuses {
+            Some(statuses) => statuses.contains(&status.as_u16()),
+            None => status.is_success(),
+        }
+    }
+
+    /// Returns true if the status is an unfollowed redirect that counts as a running service.
+    pub(crate) fn accepts_redirect(&self, status: reqwest::StatusCode) -> bool {
+        status.is_redirection() && !self.redirect_is_failure
+    }
+
+    /// Returns true if the shared HTTP client can perform the check.
+    fn uses_shared_client(&self) -> bool {
+        self.redirect == RedirectPolicy::Follow && self.keep_alive && self.proxy.is_none()
+    }
+
+    /// Returns the configured proxy.
+    fn reqwest_proxy(&self) -> Result<Option<reqwest::Proxy>, ServiceCheckError> {
+        self.proxy.as_deref().map(reqwest::Proxy::all).transpose().map_err(ServiceCheckError::Client)
+    }
+
+    /// Returns the reqwest redirect policy for the check.
+    fn reqwest_redirect(&self) -> reqwest::redirect::Policy {
+        match self.redirect {
+            RedirectPolicy::Follow => reqwest::redirect::Policy::default(),
+            RedirectPolicy::None => reqwest::redirect::Policy::none(),
+            // reqwest counts the original request towards its limit.
+            RedirectPolicy::Limit(max) => reqwest::redirect::Policy::limited(max.saturating_add(1)),
+        }
+    }
+
+    /// Returns the shared client, or a dedicated client when the redirect,
+    /// keep-alive or proxy settings differ from its defaults.
+    #[cfg(feature = "service_checker")]
+    pub(crate) fn client(&self, shared: &reqwest::Client) -> Result<reqwest::Client, ServiceCheckError> {
+        if self.uses_shared_client() {
+            return Ok(shared.clone());
+        }
+        let builder = reqwest::Client::builder().redirect(self.reqwest_redirect());
+        let builder = match self.keep_alive {
+            true => builder,
+            false => builder.pool_max_idle_per_host(0),
+        };
+        let builder = match self.reqwest_proxy()? {
+            Some(proxy) => builder.proxy(proxy),
+            None => builder,
+        };
+        builder.build().map_err(ServiceCheckError::Client)
+    }
+
+    /// Returns the shared blocking client, or a dedicated client when the
+    /// redirect, keep-alive or proxy settings differ from its defaults.
+    #[cfg(feature = "service_checker_blocking")]
+    pub(crate) fn blocking_client(&self, shared: &reqwest::blocking::Client) -> Result<reqwest::blocking::Client, ServiceCheckError> {
+        if self.uses_shared_client() {
+            return Ok(shared.clone());
+        }
+        let builder = reqwest::blocking::Client::builder().redirect(self.reqwest_redirect());
+        let builder = match self.keep_alive {
+            true => builder,
+            false => builder.pool_max_idle_per_host(0),
+        };
+        let builder = match self.reqwest_proxy()? {
+            Some(proxy) => builder.proxy(proxy),
+            None => builder,
+        };
+        builder.build().map_err(ServiceCheckError::Client)
+    }
+}
+
+/// Separates a request that could not be built, which is a local
+/// misconfiguration, from a request that failed, which is categorized.
+pub(crate) fn request_failure(error: reqwest::Error) -> Result<ErrorKind, ServiceCheckError> {
+    match error.is_builder() {
+        true => Err(ServiceCheckError::Request(error)),
+        false => Ok(request_error_kind(&error)),
+    }
+}
+
+/// Categorizes a request that could not be completed. hyper reports a DNS
+/// failure as a connect error whose message starts with "dns error", so the
+/// source chain is searched for it before the connect and timeout checks.
+/// hyper does not export that error type, so the service checker tests pin
+/// the message instead.
+pub(crate) fn request_error_kind(error: &reqwest::Error) -> ErrorKind {
+    let mut source: Option<&(dyn std::error::Error + 'static)> = Some(error);
+    while let Some(cause) = source {
+        if cause.to_string().starts_with("dns error") {
+            return ErrorKind::Dns;
+        }
+        source = cause.source();
+    }
+    match () {
+        _ if error.is_timeout() => ErrorKind::Timeout,
+        _ if error.is_connect() => ErrorKind::Connection,
+        _ => ErrorKind::Other,
+    }
+}
+
+/// Categorizes an unacceptable status.
+pub(crate) fn status_error_kind(status: reqwest::StatusCode) -> ErrorKind {
+    match () {
+        _ if status.is_client_error() => ErrorKind::ClientError,
+        _ if status.is_server_error() => ErrorKind::ServerError,
+        _ => ErrorKind::Other,
+    }
+}
+
+impl Default for ServiceCheckConfig {
+    fn default() -> Self {
+        ServiceCheckConfig {
+            method: Method::GET,
+            headers: HeaderMap::new(),
+            timeout: DEFAULT_CHECK_TIMEOUT,
+            acceptable_statuses: None,
+            body_matcher: None,
+            redirect: RedirectPolicy::Follow,
+            redirect_is_failure: true,
+            keep_alive: true,
+            proxy: None,
+        }
+    }
+}
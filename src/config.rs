@@ -0,0 +1,65 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+/// The BrakeConfig struct describes an emergency brake in a form that can be
+/// loaded from a configuration file with the `serde` feature, so the brake can
+/// be tuned without recompiling. Omitted fields take their default values.
+/// ```
+/// use emergency_brake::*;
+/// let config = BrakeConfig {
+///     window_size: 10,
+///     failure_threshold: 3,
+///     trigger: Trigger::Log,
+///     ..BrakeConfig::default()
+/// };
+/// let ebrake = EBrake::from_config(config).unwrap();
+/// assert_eq!(ebrake.trigger_default(), TripOutcome::NotTripped);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct BrakeConfig {
+    /// The number of samples held in the sample window.
+    pub window_size: usize,
+
+    /// The number of failures that may be present in the window before the
+    /// emergency brake is triggered.
+    pub failure_threshold: usize,
+
+    /// The failure rate at which the emergency brake is triggered. When set,
+    /// it takes precedence over the failure threshold.
+    pub failure_rate: Option<f64>,
+
+    /// The trigger action used by `trigger_default`.
+    pub trigger: Trigger,
+
+    /// The number of samples required before the emergency brake may trigger.
+    /// Defaults to the window size.
+    pub min_samples: Option<usize>,
+}
+
+impl EBrake {
+    /// Creates a new Emergency Brake from the given configuration, validating it.
+    pub fn from_config(config: BrakeConfig) -> Result<EBrake, EBrakeError> {
+        let mut ebrake = EBrake::try_new(config.window_size, config.failure_threshold)?;
+        if let Some(failure_rate) = config.failure_rate {
+            if !(failure_rate > 0.0 && failure_rate <= 1.0) {
+                return Err(EBrakeError::InvalidFailureRate);
+            }
+            ebrake.failure_rate_threshold = Some(failure_rate);
+        }
+        if let Some(min_samples) = config.min_samples {
+            ebrake.set_min_samples(min_samples)?;
+        }
+        ebrake.default_trigger = config.trigger;
+        Ok(ebrake)
+    }
+}
+
+/// Test module for building an emergency brake from a configuration.
+#[cfg(test)]
+mod test;
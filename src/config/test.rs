@@ -0,0 +1,66 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+#[test]
+/// Test that a brake built from a configuration uses the configured values.
+fn it_should_build_from_a_config() {
+    let config = BrakeConfig {
+        window_size: 4,
+        failure_threshold: 1,
+        min_samples: Some(2),
+        trigger: Trigger::Log,
+        ..BrakeConfig::default()
+    };
+    let mut ebrake = EBrake::from_config(config).unwrap();
+    assert_eq!(ebrake.default_trigger(), &Trigger::Log);
+    assert_eq!(ebrake.min_samples(), 2);
+    ebrake.extend([false, false]);
+    assert_eq!(ebrake.trigger_default(), TripOutcome::Handled);
+}
+
+#[test]
+/// Test that an invalid configuration is rejected.
+fn it_should_reject_an_invalid_config() {
+    let config = |window_size, failure_rate, min_samples| BrakeConfig {
+        window_size,
+        failure_rate,
+        min_samples,
+        ..BrakeConfig::default()
+    };
+    assert_eq!(EBrake::from_config(config(0, None, None)).unwrap_err(), EBrakeError::ZeroWindow);
+    assert_eq!(EBrake::from_config(config(4, Some(1.5), None)).unwrap_err(), EBrakeError::InvalidFailureRate);
+    assert_eq!(EBrake::from_config(config(4, None, Some(5))).unwrap_err(), EBrakeError::MinSamplesTooLarge);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+/// Test that a brake can be built from an `[emergency_brake]` TOML section.
+fn it_should_build_from_a_toml_section() {
+    #[derive(serde::Deserialize)]
+    struct ServiceConfig {
+        emergency_brake: BrakeConfig,
+    }
+
+    let config: ServiceConfig = toml::from_str(
+        r#"
+        [emergency_brake]
+        window_size = 10
+        failure_rate = 0.5
+        trigger = "Log"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(config.emergency_brake.failure_threshold, 0);
+    assert_eq!(config.emergency_brake.min_samples, None);
+
+    let mut ebrake = EBrake::from_config(config.emergency_brake).unwrap();
+    ebrake.extend([false; 4]);
+    ebrake.extend([true; 5]);
+    assert_eq!(ebrake.trigger_default(), TripOutcome::NotTripped);
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.trigger_default(), TripOutcome::Handled);
+}
@@ -0,0 +1,64 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+/// Returns the decayed failure score of samples given newest first, with
+/// their weights. Each failure contributes `weight * decay^age`, where the
+/// newest sample has an age of zero.
+pub(crate) fn decayed_score(samples: impl Iterator<Item = (bool, usize)>, decay: f64) -> f64 {
+    samples
+        .fold((0.0, 1.0), |(score, factor), (sample, weight)| match sample {
+            true => (score, factor * decay),
+            false => (score + weight as f64 * factor, factor * decay),
+        })
+        .0
+}
+
+impl EBrake {
+    /// Enables the decay mode, where older failures count for less than recent
+    /// ones. Each failure in the window contributes `weight * decay^age` to the
+    /// decayed failure score, where `age` is the number of samples recorded
+    /// after it, and the emergency brake triggers once the score exceeds the
+    /// tolerance. A decay of `1.0` counts every failure fully. The failure
+    /// rate threshold, when set, takes precedence over the decay.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(4, 1);
+    /// ebrake.set_decay(0.5).unwrap();
+    /// ebrake.add_samples(&[true, false, false, true]);
+    /// assert_eq!(ebrake.decayed_failure_score(), 0.75);
+    /// assert!(!ebrake.should_trigger());
+    /// ```
+    pub fn set_decay(&mut self, decay: f64) -> Result<(), EBrakeError> {
+        if !(decay > 0.0 && decay <= 1.0) {
+            return Err(EBrakeError::InvalidDecay);
+        }
+
+        self.decay = Some(decay);
+        Ok(())
+    }
+
+    /// Returns the decay factor, if the decay mode is enabled.
+    pub fn decay(&self) -> Option<f64> {
+        self.decay
+    }
+
+    /// Returns the decayed failure score of the window. Without a decay, this
+    /// is the failure weight.
+    pub fn decayed_failure_score(&self) -> f64 {
+        decayed_score(self.weighted_newest_first(), self.decay.unwrap_or(1.0))
+    }
+
+    /// Iterates over the samples from newest to oldest, with their weights.
+    pub(crate) fn weighted_newest_first(&self) -> impl Iterator<Item = (bool, usize)> + '_ {
+        let weights = self.weights.iter().flatten().rev().copied().chain(std::iter::repeat(1));
+        self.data.iter().rev().zip(weights)
+    }
+}
+
+/// Test module for the decay mode.
+#[cfg(test)]
+mod test;
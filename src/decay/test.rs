@@ -0,0 +1,64 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+#[test]
+/// Test that an old failure eventually stops contributing enough to trip.
+fn it_should_stop_tripping_as_a_failure_ages() {
+    let mut ebrake = EBrake::new(4, 1);
+    ebrake.set_decay(0.5).unwrap();
+    ebrake.extend([true, true, true]);
+    ebrake.add_weighted_sample(false, 3);
+    assert_eq!(ebrake.decayed_failure_score(), 3.0);
+    assert!(ebrake.should_trigger());
+
+    ebrake.add_sample(true);
+    assert_eq!(ebrake.decayed_failure_score(), 1.5);
+    assert!(ebrake.should_trigger());
+
+    ebrake.add_sample(true);
+    assert_eq!(ebrake.decayed_failure_score(), 0.75);
+    assert!(!ebrake.should_trigger());
+    // Without the decay, the failure would still trip the brake.
+    assert!(ebrake.failure_weight() > 1);
+}
+
+#[test]
+/// Test that recent failures outweigh the same number of older ones.
+fn it_should_weigh_recent_failures_more() {
+    let (mut recent, mut old) = (EBrake::new(6, 1), EBrake::new(6, 1));
+    for ebrake in [&mut recent, &mut old] {
+        ebrake.set_decay(0.8).unwrap();
+    }
+    recent.extend([true, true, true, true, false, false]);
+    old.extend([false, false, true, true, true, true]);
+    assert!(recent.should_trigger());
+    assert!(!old.should_trigger());
+    assert!(recent.decayed_failure_score() > old.decayed_failure_score());
+}
+
+#[test]
+/// Test that an invalid decay is rejected.
+fn it_should_reject_an_invalid_decay() {
+    let mut ebrake = EBrake::new(4, 1);
+    for decay in [0.0, -0.5, 1.5, f64::NAN] {
+        assert_eq!(ebrake.set_decay(decay), Err(EBrakeError::InvalidDecay));
+    }
+    assert_eq!(ebrake.decay(), None);
+    assert!(ebrake.set_decay(1.0).is_ok());
+}
+
+#[test]
+/// Test that would_trigger predicts the decayed score after the next sample.
+fn it_should_predict_a_decayed_trip() {
+    let mut ebrake = EBrake::new(3, 1);
+    ebrake.set_decay(0.5).unwrap();
+    for sample in [false, false, true, false, true, true, false, false, false, true] {
+        let predicted = ebrake.would_trigger(sample);
+        ebrake.add_sample(sample);
+        assert_eq!(predicted, ebrake.should_trigger());
+    }
+}
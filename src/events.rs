@@ -0,0 +1,86 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::{Mutex, OnceLock, PoisonError};
+use std::time::Instant;
+
+/// The number of sample events each subscriber can buffer. Once a
+/// subscriber's buffer is full, further events are dropped for it until it
+/// catches up, so a slow subscriber never stalls the brake.
+pub const SAMPLE_EVENT_CAPACITY: usize = 1024;
+
+/// The SampleEvent struct describes a single sample recorded by an emergency
+/// brake, as sent to the receivers returned by `EBrake::subscribe`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SampleEvent {
+    /// When the sample was recorded, as measured by the brake's clock.
+    pub timestamp: Instant,
+
+    /// The sample, where `true` is a success.
+    pub sample: bool,
+
+    /// The number of failures in the window after the sample was recorded.
+    pub failures: usize,
+}
+
+/// The senders of the brake's subscribers. The list is only created by the
+/// first subscription, so without subscribers publishing is a single check.
+#[derive(Debug, Default)]
+pub(crate) struct Subscribers(OnceLock<Mutex<Vec<SyncSender<SampleEvent>>>>);
+
+impl Subscribers {
+    /// Sends the event to every subscriber, dropping it for any subscriber
+    /// whose buffer is full, and forgetting subscribers that have hung up.
+    pub(crate) fn publish(&self, event: impl FnOnce() -> SampleEvent) {
+        let Some(senders) = self.0.get() else {
+            return;
+        };
+        let event = event();
+        senders
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|sender| !matches!(sender.try_send(event), Err(TrySendError::Disconnected(_))));
+    }
+
+    fn subscribe(&self) -> Receiver<SampleEvent> {
+        let (sender, receiver) = mpsc::sync_channel(SAMPLE_EVENT_CAPACITY);
+        self.0.get_or_init(Mutex::default).lock().unwrap_or_else(PoisonError::into_inner).push(sender);
+        receiver
+    }
+}
+
+/// A cloned brake starts without subscribers, so events are not duplicated.
+impl Clone for Subscribers {
+    fn clone(&self) -> Self {
+        Subscribers::default()
+    }
+}
+
+impl EBrake {
+    /// Subscribes to the brake's samples, returning a receiver of an event for
+    /// each sample recorded from now on, such as to stream them to a time
+    /// series sink. Samples that a batch larger than the window discards are
+    /// not sent. Each receiver buffers up to `SAMPLE_EVENT_CAPACITY` events.
+    /// Dropping the receiver ends the subscription.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(10, 3);
+    /// let events = ebrake.subscribe();
+    /// ebrake.add_sample(false);
+    /// let event = events.try_recv().unwrap();
+    /// assert!(!event.sample);
+    /// assert_eq!(event.failures, 1);
+    /// ```
+    pub fn subscribe(&self) -> Receiver<SampleEvent> {
+        self.subscribers.subscribe()
+    }
+}
+
+/// Test module for the sample events.
+#[cfg(test)]
+mod test;
@@ -0,0 +1,64 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+#[test]
+/// Test that a subscriber receives an event for each sample with the resulting failure count.
+fn it_should_send_an_event_per_sample() {
+    let clock = MockClock::new();
+    let mut ebrake = EBrake::new(2, 0);
+    ebrake.set_clock(clock.clone());
+    let events = ebrake.subscribe();
+
+    let start = clock.now();
+    for sample in [false, true, true] {
+        ebrake.add_sample(sample);
+        clock.advance(Duration::from_secs(1));
+    }
+
+    let received = events.try_iter().collect::<Vec<_>>();
+    assert_eq!(received.iter().map(|event| event.sample).collect::<Vec<_>>(), [false, true, true]);
+    assert_eq!(received.iter().map(|event| event.failures).collect::<Vec<_>>(), [1, 1, 0]);
+    assert_eq!(received[2].timestamp - start, Duration::from_secs(2));
+}
+
+#[test]
+/// Test that every subscriber receives the events, and that hung up subscribers are dropped.
+fn it_should_send_events_to_every_subscriber() {
+    let mut ebrake = EBrake::new(4, 1);
+    let first = ebrake.subscribe();
+    let second = ebrake.subscribe();
+    ebrake.add_sample(true);
+    drop(first);
+    ebrake.add_sample(false);
+
+    assert_eq!(second.try_iter().count(), 2);
+    assert_eq!(ebrake.subscribers.0.get().unwrap().lock().unwrap().len(), 1);
+}
+
+#[test]
+/// Test that a full subscriber drops events instead of blocking the brake.
+fn it_should_drop_events_for_a_full_subscriber() {
+    let mut ebrake = EBrake::new(4, 1);
+    let events = ebrake.subscribe();
+    for _ in 0..(SAMPLE_EVENT_CAPACITY + 10) {
+        ebrake.add_sample(true);
+    }
+    assert_eq!(events.try_iter().count(), SAMPLE_EVENT_CAPACITY);
+
+    ebrake.add_sample(false);
+    assert_eq!(events.try_recv().unwrap().failures, 1);
+}
+
+#[test]
+/// Test that a cloned brake does not send events to the original's subscribers.
+fn it_should_not_share_subscribers_with_a_clone() {
+    let ebrake = EBrake::new(4, 1);
+    let events = ebrake.subscribe();
+    let mut clone = ebrake.clone();
+    clone.add_sample(false);
+    assert!(events.try_recv().is_err());
+}
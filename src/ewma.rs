@@ -0,0 +1,125 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+/// The EwmaBrake tracks an exponentially weighted moving average of the
+/// failure indicator instead of a window of samples. Each sample updates the
+/// average as `ewma = alpha * failure + (1 - alpha) * ewma`, where `failure`
+/// is `1.0` for a failure and `0.0` for a success, and the brake trips once
+/// the average reaches the threshold. Recent samples weigh the most, and older
+/// ones fade smoothly rather than dropping out of a buffer, so no samples are
+/// stored and it can be used without the `std` feature. The average starts
+/// at `0.0`, as if the dependency had been healthy.
+/// ```
+/// use emergency_brake::*;
+/// let mut ebrake = EwmaBrake::new(0.2, 0.5).unwrap();
+/// for _ in 0..3 {
+///     ebrake.add_sample(false);
+/// }
+/// assert!(!ebrake.should_trigger());
+/// for _ in 0..2 {
+///     ebrake.add_sample(false);
+/// }
+/// assert!(ebrake.should_trigger());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EwmaBrake {
+    alpha: f64,
+    ewma: f64,
+    threshold: f64,
+}
+
+impl EwmaBrake {
+    /// Creates a new EWMA brake with the given smoothing factor and threshold.
+    /// A larger `alpha` reacts faster to new samples. Both `alpha` and the
+    /// threshold must be within `(0.0, 1.0]`.
+    pub fn new(alpha: f64, threshold: f64) -> Result<Self, EBrakeError> {
+        if !(alpha > 0.0 && alpha <= 1.0) {
+            return Err(EBrakeError::InvalidAlpha);
+        }
+
+        if !(threshold > 0.0 && threshold <= 1.0) {
+            return Err(EBrakeError::InvalidFailureRate);
+        }
+
+        Ok(EwmaBrake { alpha, ewma: 0.0, threshold })
+    }
+
+    /// Returns the current moving average of the failure indicator.
+    pub fn ewma(&self) -> f64 {
+        self.ewma
+    }
+
+    /// Returns the smoothing factor.
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Returns the average at which the brake trips.
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+}
+
+impl EmergencyBrake for EwmaBrake {
+    fn add_sample(&mut self, sample: bool) {
+        let failure = match sample {
+            true => 0.0,
+            false => 1.0,
+        };
+        self.ewma = self.alpha * failure + (1.0 - self.alpha) * self.ewma;
+    }
+
+    fn should_trigger(&self) -> bool {
+        self.ewma >= self.threshold
+    }
+
+    fn trigger(&self, trigger: &Trigger) -> TripOutcome {
+        match self.should_trigger() {
+            true => {
+                #[cfg(feature = "std")]
+                error!(ewma = self.ewma, threshold = self.threshold, "Emergency brake triggered!");
+                trigger.execute()
+            },
+            false => TripOutcome::NotTripped,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn trigger_abort(&self) -> bool {
+        match self.should_trigger() {
+            true => {
+                error!("Emergency brake abort triggered!");
+                process::abort();
+            },
+            false => false,
+        }
+    }
+
+    fn trigger_panic(&self) -> bool {
+        match self.should_trigger() {
+            true => {
+                #[cfg(feature = "std")]
+                error!("Emergency brake panic triggered!");
+                panic!("Emergency brake panic triggered!");
+            },
+            false => false,
+        }
+    }
+
+    fn trigger_on_sample(&mut self, sample: bool, trigger: &Trigger) -> TripOutcome {
+        self.add_sample(sample);
+        self.trigger(trigger)
+    }
+
+    fn reset(&mut self) {
+        self.ewma = 0.0;
+    }
+}
+
+/// Test module for the EWMA Emergency Brake.
+#[cfg(test)]
+mod test;
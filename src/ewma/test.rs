@@ -0,0 +1,79 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+#[test]
+/// Test that alphas and thresholds outside `(0.0, 1.0]` are rejected.
+fn it_should_reject_an_invalid_configuration() {
+    for alpha in [0.0, -0.5, 1.5, f64::NAN] {
+        assert_eq!(EwmaBrake::new(alpha, 0.5).unwrap_err(), EBrakeError::InvalidAlpha);
+    }
+    for threshold in [0.0, 1.1, f64::NAN] {
+        assert_eq!(EwmaBrake::new(0.5, threshold).unwrap_err(), EBrakeError::InvalidFailureRate);
+    }
+    let ebrake = EwmaBrake::new(1.0, 1.0).unwrap();
+    assert_eq!((ebrake.alpha(), ebrake.threshold(), ebrake.ewma()), (1.0, 1.0, 0.0));
+}
+
+#[test]
+/// Test that the average converges to the steady failure rate without tripping below the threshold.
+fn it_should_converge_to_a_steady_failure_rate() {
+    let mut ebrake = EwmaBrake::new(0.05, 0.5).unwrap();
+    for i in 0..2000 {
+        // One failure in every five samples.
+        ebrake.add_sample(i % 5 != 0);
+        assert!(!ebrake.should_trigger());
+    }
+    assert!((ebrake.ewma() - 0.2).abs() < 0.05, "ewma {}", ebrake.ewma());
+
+    let mut ebrake = EwmaBrake::new(0.05, 0.5).unwrap();
+    for _ in 0..200 {
+        ebrake.add_sample(false);
+    }
+    assert!(ebrake.ewma() > 0.99);
+    assert!(ebrake.should_trigger());
+}
+
+#[test]
+/// Test that a burst of failures trips the brake, which recovers as successes follow.
+fn it_should_trip_on_a_burst_and_recover() {
+    let mut ebrake = EwmaBrake::new(0.3, 0.6).unwrap();
+    for _ in 0..50 {
+        ebrake.add_sample(true);
+    }
+    assert_eq!(ebrake.ewma(), 0.0);
+
+    // 1 - 0.7^2 = 0.51 and 1 - 0.7^3 = 0.657.
+    assert!(!ebrake.trigger_on_sample(false, &Trigger::Log).is_tripped());
+    assert!(!ebrake.trigger_on_sample(false, &Trigger::Log).is_tripped());
+    assert!(ebrake.trigger_on_sample(false, &Trigger::Log).is_tripped());
+
+    // 0.657 * 0.7 = 0.46.
+    ebrake.add_sample(true);
+    assert!(!ebrake.should_trigger());
+
+    ebrake.add_sample(false);
+    ebrake.reset();
+    assert_eq!(ebrake.ewma(), 0.0);
+}
+
+#[test]
+/// Test that a single failure among successes does not trip a slowly reacting brake.
+fn it_should_smooth_out_an_isolated_failure() {
+    let mut ebrake = EwmaBrake::new(0.1, 0.3).unwrap();
+    for i in 0..100 {
+        ebrake.add_sample(i != 50);
+        assert!(!ebrake.should_trigger());
+    }
+}
+
+#[test]
+#[should_panic(expected = "Emergency brake triggered!")]
+/// Test that the default trigger panics when the EWMA brake trips.
+fn it_should_panic_on_the_default_trigger() {
+    let mut ebrake = EwmaBrake::new(1.0, 1.0).unwrap();
+    ebrake.trigger_on_sample(false, &Trigger::default());
+}
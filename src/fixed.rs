@@ -0,0 +1,164 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+/// The EBrakeN is an emergency brake with a window of `N` samples fixed at
+/// compile time. The samples are stored inline in a `[bool; N]`, so no
+/// allocation is required and it can be used without the `std` feature.
+/// It trips on the same terms as an `EBrake` created with
+/// `EBrake::new(N, tolerance)`.
+#[derive(Clone, Debug)]
+pub struct EBrakeN<const N: usize> {
+    data: [bool; N],
+    failures: usize,
+    head: usize,
+    len: usize,
+    successes: usize,
+    tolerance: usize,
+    trip_above: usize,
+}
+
+impl<const N: usize> EBrakeN<N> {
+    /// Creates a new emergency brake with a window of `N` samples and the given tolerance.
+    /// A window of zero samples is rejected at compile time.
+    ///
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrakeN::<25>::new(3);
+    /// for _ in 0..25 {
+    ///     ebrake.add_sample(true);
+    /// }
+    /// assert_eq!(ebrake.trigger(&Trigger::Panic), TripOutcome::NotTripped);
+    /// ```
+    pub const fn new(tolerance: usize) -> Self {
+        const { assert!(N > 0, "sample window size must be greater than zero") };
+        EBrakeN {
+            data: [false; N],
+            failures: 0,
+            head: 0,
+            len: 0,
+            successes: 0,
+            tolerance,
+            trip_above: usize::MAX,
+        }
+    }
+
+    /// Creates a new emergency brake, returning an error if the tolerance
+    /// is not less than `N`, since such a brake could never be triggered.
+    pub const fn try_new(tolerance: usize) -> Result<Self, EBrakeError> {
+        match tolerance < N {
+            true => Ok(Self::new(tolerance)),
+            false => Err(EBrakeError::ToleranceTooLarge),
+        }
+    }
+
+    /// Returns the number of failures currently in the sample window.
+    pub fn current_failures(&self) -> usize {
+        self.failures
+    }
+
+    /// Returns the number of successes currently in the sample window.
+    pub fn current_successes(&self) -> usize {
+        self.successes
+    }
+
+    /// Returns the number of samples currently held in the window.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if no samples have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns true once the sample window holds `N` samples.
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+}
+
+impl<const N: usize> EmergencyBrake for EBrakeN<N> {
+    fn add_sample(&mut self, sample: bool) {
+        let index = (self.head + self.len) % N;
+        if self.len == N {
+            match self.data[self.head] {
+                true => self.successes -= 1,
+                false => self.failures -= 1,
+            }
+            self.head = (self.head + 1) % N;
+        } else {
+            self.len += 1;
+            if self.len == N {
+                // The window stays full from now on, so the tolerance applies.
+                self.trip_above = self.tolerance;
+            }
+        }
+        match sample {
+            true => self.successes += 1,
+            false => self.failures += 1,
+        }
+        self.data[index] = sample;
+    }
+
+    fn should_trigger(&self) -> bool {
+        // `trip_above` caches whether the window has filled: it is
+        // `usize::MAX`, which no failure count exceeds, until the window is
+        // full, and the tolerance afterwards. This keeps the hot path to a
+        // single comparison.
+        self.failures > self.trip_above
+    }
+
+    fn trigger(&self, trigger: &Trigger) -> TripOutcome {
+        match self.should_trigger() {
+            true => {
+                #[cfg(feature = "std")]
+                error!(failures = self.failures, samples = N, "Emergency brake triggered!");
+                trigger.execute()
+            },
+            false => TripOutcome::NotTripped,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn trigger_abort(&self) -> bool {
+        match self.should_trigger() {
+            true => {
+                error!("Emergency brake abort triggered!");
+                process::abort();
+            },
+            false => false,
+        }
+    }
+
+    fn trigger_panic(&self) -> bool {
+        match self.should_trigger() {
+            true => {
+                #[cfg(feature = "std")]
+                error!("Emergency brake panic triggered!");
+                panic!("Emergency brake panic triggered!");
+            },
+            false => false,
+        }
+    }
+
+    fn trigger_on_sample(&mut self, sample: bool, trigger: &Trigger) -> TripOutcome {
+        self.add_sample(sample);
+        self.trigger(trigger)
+    }
+
+    fn reset(&mut self) {
+        self.failures = 0;
+        self.head = 0;
+        self.len = 0;
+        self.successes = 0;
+        self.trip_above = usize::MAX;
+    }
+}
+
+/// Test module for the fixed size Emergency Brake.
+#[cfg(test)]
+mod test;
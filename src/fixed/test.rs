@@ -0,0 +1,169 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+use crate::test_util::random_samples;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+static CALLBACK_FIRED: AtomicUsize = AtomicUsize::new(0);
+
+fn record_callback() {
+    CALLBACK_FIRED.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test]
+/// Test that a new fixed brake starts with an empty window.
+fn it_should_create_an_empty_window() {
+    let ebrake = EBrakeN::<4>::new(1);
+    assert!(ebrake.is_empty());
+    assert!(!ebrake.is_full());
+    assert_eq!(ebrake.len(), 0);
+    assert!(!ebrake.should_trigger());
+}
+
+#[test]
+/// Test that a tolerance that could never be exceeded is rejected.
+fn it_should_reject_a_tolerance_that_is_too_large() {
+    assert_eq!(EBrakeN::<3>::try_new(3).unwrap_err(), EBrakeError::ToleranceTooLarge);
+    assert!(EBrakeN::<3>::try_new(2).is_ok());
+}
+
+#[test]
+/// Test that the fixed brake can be placed in a static without allocation.
+fn it_should_construct_in_a_const_context() {
+    static EBRAKE: EBrakeN<8> = EBrakeN::new(2);
+    assert!(EBRAKE.is_empty());
+}
+
+#[test]
+/// Test that the fixed brake does not trigger until the window is full.
+fn it_should_not_trigger_before_window_is_full() {
+    let mut ebrake = EBrakeN::<3>::new(1);
+    assert!(!ebrake.trigger_on_sample(false, &Trigger::Log).is_tripped());
+    assert!(!ebrake.trigger_on_sample(false, &Trigger::Log).is_tripped());
+    assert!(ebrake.trigger_on_sample(false, &Trigger::Log).is_tripped());
+}
+
+#[test]
+/// Test that the oldest sample is evicted once the window rolls over.
+fn it_should_track_current_counts_across_rollover() {
+    let mut ebrake = EBrakeN::<3>::new(1);
+    for sample in [false, false, true] {
+        ebrake.add_sample(sample);
+    }
+    assert!(ebrake.should_trigger());
+
+    ebrake.add_sample(true);
+    assert_eq!(ebrake.current_failures(), 1);
+    assert_eq!(ebrake.current_successes(), 2);
+    assert!(!ebrake.should_trigger());
+
+    ebrake.add_sample(false);
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.len(), 3);
+    assert_eq!(ebrake.current_failures(), 2);
+    assert!(ebrake.should_trigger());
+}
+
+#[test]
+/// Test that reset clears the window while keeping the tolerance.
+fn it_should_reset_the_sample_window() {
+    let mut ebrake = EBrakeN::<2>::new(1);
+    ebrake.add_sample(false);
+    ebrake.add_sample(false);
+    assert!(ebrake.should_trigger());
+
+    ebrake.reset();
+    assert!(ebrake.is_empty());
+    assert_eq!(ebrake.current_failures(), 0);
+    ebrake.add_sample(false);
+    ebrake.add_sample(false);
+    assert!(ebrake.should_trigger());
+}
+
+#[test]
+/// Test that a callback trigger runs when the fixed brake trips.
+fn it_should_invoke_the_callback_trigger() {
+    static TRIGGER: Trigger = Trigger::Callback(record_callback);
+    let mut ebrake = EBrakeN::<2>::new(0);
+    assert!(!ebrake.trigger_on_sample(false, &TRIGGER).is_tripped());
+    assert_eq!(CALLBACK_FIRED.load(Ordering::SeqCst), 0);
+
+    assert!(ebrake.trigger_on_sample(true, &TRIGGER).is_tripped());
+    assert_eq!(CALLBACK_FIRED.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+#[should_panic(expected = "Emergency brake triggered!")]
+/// Test that the default trigger panics when the fixed brake trips.
+fn it_should_panic_on_the_default_trigger() {
+    let mut ebrake = EBrakeN::<1>::new(0);
+    ebrake.trigger_on_sample(false, &Trigger::default());
+}
+
+/// Feeds the same pseudo-random samples to a fixed and a dynamic brake,
+/// asserting that both report the same window after every sample.
+#[cfg(feature = "std")]
+fn assert_parity<const N: usize>(tolerance: usize) {
+    let mut fixed = EBrakeN::<N>::new(tolerance);
+    let mut dynamic = EBrake::new(N, tolerance);
+    for sample in random_samples(N * 20, 0x2545_f491_4f6c_dd1d) {
+        assert_eq!(fixed.trigger_on_sample(sample, &Trigger::Log), dynamic.trigger_on_sample(sample, &Trigger::Log));
+        assert_eq!(fixed.should_trigger(), dynamic.should_trigger());
+        assert_eq!(fixed.current_failures(), dynamic.current_failures());
+        assert_eq!(fixed.current_successes(), dynamic.current_successes());
+        assert_eq!(fixed.len(), dynamic.len());
+        assert_eq!(fixed.is_full(), dynamic.is_full());
+    }
+
+    fixed.reset();
+    dynamic.reset();
+    assert_eq!(fixed.len(), dynamic.len());
+    assert_eq!(fixed.should_trigger(), dynamic.should_trigger());
+}
+
+#[cfg(feature = "std")]
+#[test]
+/// Test that the fixed brake behaves like the dynamic brake for several window sizes.
+fn it_should_match_the_dynamic_brake() {
+    assert_parity::<1>(0);
+    assert_parity::<3>(1);
+    assert_parity::<8>(2);
+    assert_parity::<25>(3);
+    assert_parity::<64>(20);
+}
+
+/// Feeds pseudo-random samples to a fixed brake, asserting after every sample
+/// and reset that the cached trip check matches the computed condition.
+fn assert_cached_check<const N: usize>(tolerance: usize) {
+    let mut ebrake = EBrakeN::<N>::new(tolerance);
+    for (round, sample) in random_samples(N * 30, 0x9e37_79b9_7f4a_7c15).enumerate() {
+        ebrake.add_sample(sample);
+        assert_eq!(ebrake.should_trigger(), ebrake.is_full() && ebrake.current_failures() > tolerance);
+        if round == N * 10 {
+            ebrake.reset();
+            assert!(!ebrake.should_trigger());
+        }
+    }
+}
+
+#[test]
+/// Test that the cached trip check matches the computed condition across rollovers and resets.
+fn it_should_match_the_computed_trip_condition() {
+    assert_cached_check::<1>(0);
+    assert_cached_check::<2>(0);
+    assert_cached_check::<5>(1);
+    assert_cached_check::<16>(4);
+    assert_cached_check::<64>(20);
+
+    // A window that has not filled does not trip, however many failures it holds.
+    let mut ebrake = EBrakeN::<4>::new(0);
+    for _ in 0..3 {
+        ebrake.add_sample(false);
+    }
+    assert!(!ebrake.should_trigger());
+    ebrake.add_sample(false);
+    assert!(ebrake.should_trigger());
+}
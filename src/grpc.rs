@@ -0,0 +1,62 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+use service_checker::retry_check;
+use tonic_health::pb::health_check_response::ServingStatus;
+use tonic_health::pb::health_client::HealthClient;
+use tonic_health::pb::HealthCheckRequest;
+
+/// Issues a `grpc.health.v1.Health/Check` RPC, returning true only for `SERVING`.
+/// Connecting and the RPC together must complete within the configured timeout.
+async fn check_grpc(addr: &str, service: &str, config: &ServiceCheckConfig) -> bool {
+    let check = async {
+        let channel = tonic::transport::Endpoint::from_shared(addr.to_string()).ok()?.connect().await.ok()?;
+        let request = HealthCheckRequest { service: service.to_string() };
+        let response = HealthClient::new(channel).check(request).await.ok()?;
+        Some(response.into_inner().status() == ServingStatus::Serving)
+    };
+    matches!(tokio::time::timeout(config.timeout, check).await, Ok(Some(true)))
+}
+
+impl EBrake {
+    /// Check a service with the gRPC Health Checking Protocol. This takes the
+    /// address of the gRPC server, such as `http://10.0.0.1:50051`, and the
+    /// name of the service to check, where an empty name asks for the health
+    /// of the server as a whole. Returns true if the server reports the
+    /// service as `SERVING`, and false for any other status, an unknown
+    /// service, or a failed RPC. The check times out after `DEFAULT_CHECK_TIMEOUT`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "grpc_health")))]
+    pub async fn check_grpc_endpoint(&self, addr: &str, service: &str) -> bool {
+        self.check_grpc_endpoint_with(addr, service, &WatchConfig::default()).await
+    }
+
+    /// Similar to check_grpc_endpoint, but uses the timeout of the supplied
+    /// configuration's check, and retries a failed check according to its
+    /// retry policy, like the HTTP watchers.
+    #[cfg_attr(docsrs, doc(cfg(feature = "grpc_health")))]
+    pub async fn check_grpc_endpoint_with(&self, addr: &str, service: &str, config: &WatchConfig) -> bool {
+        retry_check(config.retry.as_ref(), || check_grpc(addr, service, &config.check)).await
+    }
+}
+
+impl SharedEBrake {
+    /// Similar to `EBrake::check_grpc_endpoint`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "grpc_health")))]
+    pub async fn check_grpc_endpoint(&self, addr: &str, service: &str) -> bool {
+        self.check_grpc_endpoint_with(addr, service, &WatchConfig::default()).await
+    }
+
+    /// Similar to `EBrake::check_grpc_endpoint_with`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "grpc_health")))]
+    pub async fn check_grpc_endpoint_with(&self, addr: &str, service: &str, config: &WatchConfig) -> bool {
+        retry_check(config.retry.as_ref(), || check_grpc(addr, service, &config.check)).await
+    }
+}
+
+/// Test module for the gRPC health checks.
+#[cfg(test)]
+mod test;
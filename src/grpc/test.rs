@@ -0,0 +1,71 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+
+/// Starts an in-process gRPC health server reporting `payments` as serving
+/// and `ledger` as not serving. Returns the server's address.
+async fn serve_health() -> String {
+    let (mut reporter, service) = tonic_health::server::health_reporter();
+    reporter.set_service_status("payments", tonic_health::ServingStatus::Serving).await;
+    reporter.set_service_status("ledger", tonic_health::ServingStatus::NotServing).await;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(tonic::transport::Server::builder().add_service(service).serve_with_incoming(TcpListenerStream::new(listener)));
+    addr
+}
+
+#[tokio::test]
+/// Test that only a serving service is reported as healthy.
+async fn it_should_treat_serving_as_healthy() {
+    let addr = serve_health().await;
+    let ebrake = EBrake::new(10, 3);
+    assert!(ebrake.check_grpc_endpoint(&addr, "payments").await);
+    assert!(!ebrake.check_grpc_endpoint(&addr, "ledger").await);
+    assert!(!ebrake.check_grpc_endpoint(&addr, "unknown").await);
+    // The server as a whole is serving.
+    assert!(ebrake.check_grpc_endpoint(&addr, "").await);
+}
+
+#[tokio::test]
+/// Test that an unreachable server is reported as a failure, after the configured retries.
+async fn it_should_fail_an_unreachable_server() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+
+    let config = WatchConfig {
+        check: ServiceCheckConfig {
+            timeout: Duration::from_millis(500),
+            ..ServiceCheckConfig::default()
+        },
+        retry: Some(RetryPolicy {
+            max_retries: 2,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(1),
+        }),
+        ..WatchConfig::default()
+    };
+    let ebrake = SharedEBrake::new(EBrake::new(10, 3));
+    assert!(!ebrake.check_grpc_endpoint_with(&addr, "payments", &config).await);
+    assert!(!ebrake.check_grpc_endpoint(&addr, "payments").await);
+}
+
+#[tokio::test]
+/// Test that a gRPC check result can be recorded as a sample.
+async fn it_should_record_grpc_checks_as_samples() {
+    let addr = serve_health().await;
+    let mut ebrake = EBrake::new(2, 0);
+    for service in ["payments", "ledger"] {
+        let healthy = ebrake.check_grpc_endpoint(&addr, service).await;
+        ebrake.add_sample(healthy);
+    }
+    assert_eq!(ebrake.to_vec(), [true, false]);
+    assert!(ebrake.should_trigger());
+}
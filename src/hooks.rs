@@ -0,0 +1,117 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+use std::sync::Arc;
+
+/// A shared callback registered on an emergency brake.
+pub(crate) struct Hook<F: ?Sized>(pub(crate) Arc<F>);
+
+impl<F: ?Sized> Clone for Hook<F> {
+    fn clone(&self) -> Self {
+        Hook(Arc::clone(&self.0))
+    }
+}
+
+impl<F: ?Sized> fmt::Debug for Hook<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Hook")
+    }
+}
+
+impl<F: ?Sized> std::ops::Deref for Hook<F> {
+    type Target = F;
+
+    fn deref(&self) -> &F {
+        &self.0
+    }
+}
+
+/// A hook that receives a snapshot of the window.
+pub(crate) type SnapshotHook = Hook<dyn Fn(&BrakeSnapshot) + Send + Sync>;
+
+/// A hook that runs without arguments.
+pub(crate) type RecoverHook = Hook<dyn Fn() + Send + Sync>;
+
+impl EBrake {
+    /// Sets a hook that runs whenever the emergency brake is triggered, right
+    /// before the trigger action is taken. The hook receives a snapshot of the
+    /// window, and runs regardless of whether the action aborts, panics or logs.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(2, 0);
+    /// ebrake.set_on_trigger(|snapshot| println!("tripped with {} failures", snapshot.failures));
+    /// ebrake.add_samples(&[false, false]);
+    /// assert_eq!(ebrake.trigger(&Trigger::Log), TripOutcome::Handled);
+    /// ```
+    pub fn set_on_trigger(&mut self, hook: impl Fn(&BrakeSnapshot) + Send + Sync + 'static) {
+        self.on_trigger = Some(Hook(Arc::new(hook)));
+    }
+
+    /// Sets a hook that runs when the emergency brake recovers, that is when
+    /// it goes from tripped back to healthy as failures age out of the window.
+    /// With a cooldown, the brake recovers when a probe closes it again. A
+    /// latched brake only recovers through `reset`, which does not run the hook.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(2, 0);
+    /// ebrake.set_on_recover(|| println!("brake recovered"));
+    /// ebrake.add_samples(&[false, false]);
+    /// ebrake.add_samples(&[true, true]);
+    /// assert!(!ebrake.should_trigger());
+    /// ```
+    pub fn set_on_recover(&mut self, hook: impl Fn() + Send + Sync + 'static) {
+        self.on_recover = Some(Hook(Arc::new(hook)));
+    }
+
+    /// Sets a hook that runs after each successful sample is recorded, such as
+    /// to report the progress of half-open probes. The hook receives a
+    /// snapshot of the window including the sample.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(5, 1);
+    /// ebrake.set_on_success(|snapshot| println!("{}/{} samples passed", snapshot.successes, snapshot.window_size));
+    /// ebrake.add_sample(true);
+    /// ```
+    pub fn set_on_success(&mut self, hook: impl Fn(&BrakeSnapshot) + Send + Sync + 'static) {
+        self.on_success = Some(Hook(Arc::new(hook)));
+    }
+
+    /// Returns true while the brake is tripped, or waiting out a cooldown.
+    pub(crate) fn in_trip(&self) -> bool {
+        match self.cooldown {
+            Some(_) => self.latched || self.state != BrakeState::Closed,
+            None => self.should_trigger(),
+        }
+    }
+
+    /// Runs the recovery hook, if one is set.
+    pub(crate) fn notify_recover(&self) {
+        if let Some(hook) = &self.on_recover {
+            hook();
+        }
+    }
+
+    /// Runs the success hook, if one is set.
+    pub(crate) fn notify_success(&self) {
+        if let Some(hook) = &self.on_success {
+            hook(&self.snapshot());
+        }
+    }
+
+    /// Runs the side effects of a trip that happen before the trigger action.
+    pub(crate) fn notify_trip(&self) {
+        self.write_crash_report();
+        if let Some(hook) = &self.on_trigger {
+            hook(&self.snapshot());
+        }
+    }
+}
+
+
+/// Test module for the emergency brake hooks.
+#[cfg(test)]
+mod test;
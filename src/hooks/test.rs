@@ -0,0 +1,98 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+#[test]
+/// Test that the trigger hook fires once with the window statistics when the threshold is crossed.
+fn it_should_run_the_trigger_hook_once() {
+    let snapshots = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&snapshots);
+    let mut ebrake = EBrake::new(3, 1);
+    ebrake.set_on_trigger(move |snapshot| recorded.lock().unwrap().push(*snapshot));
+
+    for sample in [true, false, false] {
+        ebrake.trigger_on_sample(sample, &Trigger::Log);
+    }
+
+    let snapshots = snapshots.lock().unwrap();
+    assert_eq!(snapshots.len(), 1);
+    assert_eq!(snapshots[0].failures, 2);
+    assert_eq!(snapshots[0].successes, 1);
+    assert!(snapshots[0].tripped);
+}
+
+#[test]
+#[should_panic(expected = "Emergency brake panic triggered!")]
+/// Test that the trigger hook runs before a terminal action.
+fn it_should_run_the_trigger_hook_before_panicking() {
+    let mut ebrake = EBrake::new(1, 0);
+    ebrake.set_on_trigger(|_| panic!("Emergency brake panic triggered!"));
+    ebrake.add_sample(false);
+    ebrake.trigger(&Trigger::Abort);
+}
+
+#[test]
+/// Test that the recovery hook fires once when failures age out of the window.
+fn it_should_run_the_recovery_hook_once() {
+    let recoveries = Arc::new(AtomicUsize::new(0));
+    let recorded = Arc::clone(&recoveries);
+    let mut ebrake = EBrake::new(3, 0);
+    ebrake.set_on_recover(move || {
+        recorded.fetch_add(1, Ordering::SeqCst);
+    });
+
+    ebrake.extend([true, true, false]);
+    assert!(ebrake.should_trigger());
+    ebrake.extend([true, true]);
+    assert_eq!(recoveries.load(Ordering::SeqCst), 0);
+
+    ebrake.extend([true, true, true]);
+    assert!(!ebrake.should_trigger());
+    assert_eq!(recoveries.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+/// Test that the success hook fires once per successful sample with the updated statistics.
+fn it_should_run_the_success_hook_per_success() {
+    let snapshots = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&snapshots);
+    let mut ebrake = EBrake::new(3, 0);
+    ebrake.set_on_success(move |snapshot| recorded.lock().unwrap().push(*snapshot));
+
+    ebrake.extend([false, true, true, false]);
+    ebrake.add_weighted_sample(true, 2);
+
+    let snapshots = snapshots.lock().unwrap();
+    assert_eq!(snapshots.iter().map(|snapshot| snapshot.successes).collect::<Vec<_>>(), [1, 2, 2]);
+    assert_eq!(snapshots.iter().map(|snapshot| snapshot.failures).collect::<Vec<_>>(), [1, 1, 1]);
+    assert_eq!(snapshots.iter().map(|snapshot| snapshot.tripped).collect::<Vec<_>>(), [false, true, true]);
+    assert_eq!(snapshots[2].total_samples, 5);
+}
+
+#[test]
+/// Test that the success hook reports the progress of half-open probes.
+fn it_should_report_half_open_probe_progress() {
+    let clock = MockClock::new();
+    let mut ebrake = EBrake::new(2, 0);
+    ebrake.set_clock(clock.clone());
+    ebrake.set_cooldown(Duration::from_secs(10));
+    ebrake.set_half_open_max_probes(3);
+    let passed = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&passed);
+    ebrake.set_on_success(move |_| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+
+    ebrake.extend([false, false]);
+    clock.advance(Duration::from_secs(10));
+    for probe in 1..=3 {
+        ebrake.add_sample(true);
+        assert_eq!(passed.load(Ordering::SeqCst), probe);
+    }
+    assert_eq!(ebrake.state(), BrakeState::Closed);
+}
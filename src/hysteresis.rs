@@ -0,0 +1,55 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+impl EBrake {
+    /// Adds hysteresis to a rate based brake: once the failure rate reaches
+    /// the trip rate, the brake stays tripped until the rate falls below this
+    /// lower reset rate. A rate hovering around a single threshold then no
+    /// longer flips the brake, and its recovery hook, on every sample. The
+    /// rate must be within `(0.0, 1.0]` and must not exceed the trip rate.
+    /// It only applies to a brake created with a failure rate.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new_with_rate(10, 0.3).unwrap();
+    /// ebrake.set_reset_failure_rate(0.15).unwrap();
+    /// ebrake.add_samples(&[false, false, false, true, true, true, true, true, true, true]);
+    /// assert!(ebrake.should_trigger());
+    ///
+    /// // At 20% the brake would have reset without hysteresis.
+    /// ebrake.add_sample(true);
+    /// assert!(ebrake.should_trigger());
+    /// ebrake.add_sample(true);
+    /// assert!(!ebrake.should_trigger());
+    /// ```
+    pub fn set_reset_failure_rate(&mut self, rate: f64) -> Result<(), EBrakeError> {
+        let trip_rate = self.failure_rate_threshold.unwrap_or(1.0);
+        if !(rate > 0.0 && rate <= trip_rate) {
+            return Err(EBrakeError::InvalidFailureRate);
+        }
+
+        self.reset_failure_rate = Some(rate);
+        Ok(())
+    }
+
+    /// Returns the failure rate below which a tripped brake resets, if one is set.
+    pub fn reset_failure_rate(&self) -> Option<f64> {
+        self.reset_failure_rate
+    }
+
+    /// Returns true if the failure rate crosses the trip rate, or, while the
+    /// brake is tripped, has not yet fallen below the reset rate.
+    pub(crate) fn rate_exceeded(&self, trip_rate: f64, rate: f64) -> bool {
+        match (self.reset_failure_rate, self.last_tripped) {
+            (Some(reset_rate), true) => rate >= reset_rate,
+            _ => rate >= trip_rate,
+        }
+    }
+}
+
+/// Test module for the failure rate hysteresis.
+#[cfg(test)]
+mod test;
@@ -0,0 +1,76 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Feeds a rate oscillating between 20% and 30% to the brake, returning how often it
+/// changed between tripped and healthy and how often its recovery hook ran.
+fn count_flaps(mut ebrake: EBrake) -> (usize, usize) {
+    let recoveries = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&recoveries);
+    ebrake.set_on_recover(move || {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+
+    let pattern = [true, true, false, true, true, true, false];
+    ebrake.add_samples(&pattern.repeat(2));
+    assert!(ebrake.should_trigger());
+    let (mut flaps, mut tripped) = (0, true);
+    for sample in pattern.repeat(6) {
+        ebrake.add_sample(sample);
+        if ebrake.should_trigger() != tripped {
+            flaps += 1;
+            tripped = !tripped;
+        }
+    }
+    (flaps, recoveries.load(Ordering::SeqCst))
+}
+
+#[test]
+/// Test that hysteresis keeps a rate hovering at the threshold from flapping the brake.
+fn it_should_prevent_flapping_at_the_threshold() {
+    let single = EBrake::new_with_rate(10, 0.3).unwrap();
+    let (flaps, recoveries) = count_flaps(single);
+    assert_eq!((flaps, recoveries), (12, 6));
+
+    let mut hysteresis = EBrake::new_with_rate(10, 0.3).unwrap();
+    hysteresis.set_reset_failure_rate(0.15).unwrap();
+    assert_eq!(count_flaps(hysteresis), (0, 0));
+}
+
+#[test]
+/// Test that a tripped brake resets once the rate falls below the reset rate, and trips again at the trip rate.
+fn it_should_reset_below_the_reset_rate() {
+    let mut ebrake = EBrake::builder().window_size(10).failure_rate(0.3).reset_failure_rate(0.15).build().unwrap();
+    assert_eq!(ebrake.reset_failure_rate(), Some(0.15));
+    ebrake.add_samples(&[false, false, false, true, true, true, true, true, true, true]);
+    assert!(ebrake.should_trigger());
+    assert!(ebrake.would_trigger(true));
+
+    ebrake.add_samples(&[true, true]);
+    assert_eq!(ebrake.failure_rate(), 0.1);
+    assert!(!ebrake.should_trigger());
+
+    // Climbing back to 20% stays below the trip rate.
+    ebrake.add_samples(&[false, false]);
+    assert_eq!(ebrake.failure_rate(), 0.2);
+    assert!(!ebrake.should_trigger());
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.failure_rate(), 0.3);
+    assert!(ebrake.should_trigger());
+}
+
+#[test]
+/// Test that a reset rate outside the range or above the trip rate is rejected.
+fn it_should_reject_an_invalid_reset_rate() {
+    let mut ebrake = EBrake::new_with_rate(10, 0.3).unwrap();
+    for rate in [0.0, -0.1, 0.31, 1.5, f64::NAN] {
+        assert_eq!(ebrake.set_reset_failure_rate(rate), Err(EBrakeError::InvalidFailureRate), "rate {}", rate);
+    }
+    assert_eq!(ebrake.reset_failure_rate(), None);
+    assert!(EBrake::builder().window_size(10).failure_rate(0.2).reset_failure_rate(0.5).build().is_err());
+}
@@ -0,0 +1,49 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+impl EBrake {
+    /// Makes the emergency brake latch: once it trips, `should_trigger` keeps
+    /// returning true until `reset` is called, even after the failures age out
+    /// of the window. This matches the fail-safe semantics of a hardware brake.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(2, 0);
+    /// ebrake.set_latching(true);
+    /// ebrake.add_sample(false);
+    /// ebrake.add_samples(&[true, true]);
+    /// assert!(ebrake.should_trigger());
+    /// ebrake.reset();
+    /// assert!(!ebrake.should_trigger());
+    /// ```
+    pub fn set_latching(&mut self, latching: bool) {
+        self.latching = latching;
+        if !latching {
+            self.latched = false;
+        }
+    }
+
+    /// Returns true if the emergency brake latches after its first trip.
+    pub fn is_latching(&self) -> bool {
+        self.latching
+    }
+
+    /// Returns true if the emergency brake has tripped and latched.
+    pub fn is_latched(&self) -> bool {
+        self.latched
+    }
+
+    /// Latches the brake once the window trips, if latching is enabled.
+    pub(crate) fn update_latch(&mut self) {
+        if self.latching && !self.latched && self.window_tripped() {
+            self.latched = true;
+        }
+    }
+}
+
+/// Test module for the latching mode.
+#[cfg(test)]
+mod test;
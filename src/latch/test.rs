@@ -0,0 +1,57 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+#[test]
+/// Test that a latched brake stays tripped after the window fills with successes.
+fn it_should_stay_tripped_once_latched() {
+    let mut ebrake = EBrake::builder().window_size(3).failure_threshold(0).latching(true).build().unwrap();
+    assert!(ebrake.is_latching());
+    ebrake.extend([true, true, false]);
+    assert!(ebrake.is_latched());
+
+    ebrake.extend([true, true, true]);
+    assert_eq!(ebrake.current_failures(), 0);
+    assert!(ebrake.should_trigger());
+    assert!(ebrake.trigger(&Trigger::Log).is_tripped());
+}
+
+#[test]
+/// Test that a brake without latching recovers once the failures age out.
+fn it_should_recover_without_latching() {
+    let mut ebrake = EBrake::new(3, 0);
+    ebrake.extend([true, true, false]);
+    assert!(ebrake.should_trigger());
+
+    ebrake.extend([true, true, true]);
+    assert!(!ebrake.is_latched());
+    assert!(!ebrake.should_trigger());
+}
+
+#[test]
+/// Test that reset releases the latch.
+fn it_should_release_the_latch_on_reset() {
+    let mut ebrake = EBrake::new(2, 0);
+    ebrake.set_latching(true);
+    ebrake.extend([false, false]);
+    ebrake.reset();
+    assert!(!ebrake.is_latched());
+    assert!(!ebrake.should_trigger());
+
+    ebrake.extend([true, true]);
+    assert!(!ebrake.should_trigger());
+}
+
+#[test]
+/// Test that a latched brake stays tripped through a cooldown.
+fn it_should_latch_with_a_cooldown() {
+    let mut ebrake = EBrake::new(2, 0);
+    ebrake.set_latching(true);
+    ebrake.set_cooldown(Duration::from_secs(60));
+    ebrake.extend([true, false]);
+    ebrake.extend([true, true]);
+    assert!(ebrake.should_trigger());
+}
@@ -0,0 +1,149 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use pin_project_lite::pin_project;
+
+/// The EBrakeLayer is a `tower` layer that feeds the outcome of every request
+/// handled by the wrapped service into a `SharedEBrake`, so an HTTP server
+/// such as one built with `axum` trips its brake when its error rate spikes.
+/// A response with a 2xx or 3xx status is recorded as a success, and one with
+/// a 5xx status, or an error returned by the service, as a failure. Other
+/// statuses, such as a 4xx caused by the client, are not recorded.
+///
+/// Once the brake trips, each sample fires the layer's trigger, which is
+/// `Trigger::Log` unless another is chosen with `with_trigger`. The brake's
+/// own default trigger is not used, since it defaults to `Trigger::Panic`,
+/// which would panic inside the response future of every request. A
+/// `Panic` trigger chosen with `with_trigger` fires while the brake is
+/// locked; the lock recovers from the panic, so the brake stays usable.
+/// ```
+/// use emergency_brake::*;
+/// use tower::ServiceBuilder;
+/// let ebrake = SharedEBrake::new(EBrake::builder().window_size(100).failure_rate(0.5).build().unwrap());
+/// let service = ServiceBuilder::new()
+///     .layer(EBrakeLayer::new(ebrake.clone()))
+///     .service_fn(|_: http::Request<()>| async { Ok::<_, std::convert::Infallible>(http::Response::new(())) });
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "tower")))]
+#[derive(Clone, Debug)]
+pub struct EBrakeLayer {
+    ebrake: SharedEBrake,
+    trigger: Trigger,
+}
+
+impl EBrakeLayer {
+    /// Creates a layer that records request outcomes into the given brake,
+    /// and logs once it trips.
+    pub fn new(ebrake: SharedEBrake) -> Self {
+        EBrakeLayer::with_trigger(ebrake, Trigger::Log)
+    }
+
+    /// Creates a layer that records request outcomes into the given brake,
+    /// and fires the given trigger once it trips.
+    pub fn with_trigger(ebrake: SharedEBrake, trigger: Trigger) -> Self {
+        EBrakeLayer { ebrake, trigger }
+    }
+}
+
+impl<S> tower_layer::Layer<S> for EBrakeLayer {
+    type Service = EBrakeService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        EBrakeService {
+            ebrake: self.ebrake.clone(),
+            trigger: self.trigger,
+            inner,
+        }
+    }
+}
+
+/// The EBrakeService is the service created by an `EBrakeLayer`.
+#[cfg_attr(docsrs, doc(cfg(feature = "tower")))]
+#[derive(Clone, Debug)]
+pub struct EBrakeService<S> {
+    ebrake: SharedEBrake,
+    trigger: Trigger,
+    inner: S,
+}
+
+impl<S> EBrakeService<S> {
+    /// Returns the brake the service records into.
+    pub fn ebrake(&self) -> &SharedEBrake {
+        &self.ebrake
+    }
+}
+
+impl<S, ReqBody, ResBody> tower_service::Service<http::Request<ReqBody>> for EBrakeService<S>
+where
+    S: tower_service::Service<http::Request<ReqBody>, Response = http::Response<ResBody>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = EBrakeFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<ReqBody>) -> Self::Future {
+        EBrakeFuture {
+            ebrake: self.ebrake.clone(),
+            trigger: self.trigger,
+            future: self.inner.call(request),
+        }
+    }
+}
+
+pin_project! {
+    /// The EBrakeFuture is the response future of an `EBrakeService`, which
+    /// records the outcome once the wrapped service responds.
+    #[cfg_attr(docsrs, doc(cfg(feature = "tower")))]
+    #[derive(Debug)]
+    pub struct EBrakeFuture<F> {
+        ebrake: SharedEBrake,
+        trigger: Trigger,
+        #[pin]
+        future: F,
+    }
+}
+
+impl<F, ResBody, E> Future for EBrakeFuture<F>
+where
+    F: Future<Output = Result<http::Response<ResBody>, E>>,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let result = core::task::ready!(this.future.poll(cx));
+        let sample = match &result {
+            Ok(response) => status_sample(response.status()),
+            Err(_) => Some(false),
+        };
+        if let Some(sample) = sample {
+            this.ebrake.trigger_on_sample(sample, this.trigger);
+        }
+        Poll::Ready(result)
+    }
+}
+
+/// Returns the sample recorded for a response status, or `None` if the
+/// status says nothing about the health of the service.
+fn status_sample(status: http::StatusCode) -> Option<bool> {
+    match () {
+        _ if status.is_success() || status.is_redirection() => Some(true),
+        _ if status.is_server_error() => Some(false),
+        _ => None,
+    }
+}
+
+/// Test module for the tower middleware.
+#[cfg(test)]
+mod test;
@@ -0,0 +1,102 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+use std::convert::Infallible;
+use tower::{service_fn, Layer, ServiceExt};
+
+/// Returns a request for the given path.
+fn request(path: &str) -> http::Request<()> {
+    http::Request::builder().uri(path).body(()).unwrap()
+}
+
+/// Responds with the status given as the request path, such as `/503`.
+async fn respond(request: http::Request<()>) -> Result<http::Response<()>, Infallible> {
+    let status = request.uri().path().trim_start_matches('/').parse::<u16>().unwrap();
+    Ok(http::Response::builder().status(status).body(()).unwrap())
+}
+
+#[test]
+/// Test that statuses are sorted into successes, failures and ignored responses.
+fn it_should_classify_response_statuses() {
+    for (status, expected) in [(200, Some(true)), (204, Some(true)), (302, Some(true)), (404, None), (101, None), (500, Some(false)), (503, Some(false))] {
+        assert_eq!(status_sample(http::StatusCode::from_u16(status).unwrap()), expected, "{}", status);
+    }
+}
+
+#[tokio::test]
+/// Test that requests driven through the layer are recorded as samples.
+async fn it_should_record_request_outcomes() {
+    let ebrake = SharedEBrake::new(EBrake::builder().window_size(10).failure_threshold(5).build().unwrap());
+    let service = EBrakeLayer::new(ebrake.clone()).layer(service_fn(respond));
+
+    for path in ["/200", "/301", "/404", "/500", "/201", "/503", "/429"] {
+        let response = service.clone().oneshot(request(path)).await.unwrap();
+        assert_eq!(response.status().as_str(), &path[1..]);
+    }
+
+    let ebrake = ebrake.lock();
+    assert_eq!(ebrake.current_successes(), 3);
+    assert_eq!(ebrake.current_failures(), 2);
+    assert_eq!(ebrake.len(), 5);
+}
+
+#[tokio::test]
+/// Test that an error returned by the wrapped service counts as a failure.
+async fn it_should_record_a_service_error_as_a_failure() {
+    let ebrake = SharedEBrake::new(EBrake::new(5, 2));
+    let service = EBrakeLayer::new(ebrake.clone()).layer(service_fn(|_: http::Request<()>| async { Err::<http::Response<()>, _>("unavailable") }));
+    assert_eq!(service.ebrake().lock().len(), 0);
+
+    assert_eq!(service.oneshot(request("/")).await.unwrap_err(), "unavailable");
+    assert_eq!(ebrake.lock().current_failures(), 1);
+}
+
+static SPIKES: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+fn record_spike() {
+    SPIKES.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+}
+
+#[tokio::test]
+/// Test that a spike of server errors fires the layer's trigger.
+async fn it_should_trip_on_an_error_spike() {
+    let ebrake = SharedEBrake::new(EBrake::builder().window_size(4).failure_threshold(1).build().unwrap());
+    let service = EBrakeLayer::with_trigger(ebrake.clone(), Trigger::Callback(record_spike)).layer(service_fn(respond));
+
+    for path in ["/200", "/200", "/500", "/200"] {
+        service.clone().oneshot(request(path)).await.unwrap();
+    }
+    assert_eq!(SPIKES.load(std::sync::atomic::Ordering::SeqCst), 0);
+
+    service.clone().oneshot(request("/502")).await.unwrap();
+    assert!(ebrake.should_trigger());
+    assert_eq!(SPIKES.load(std::sync::atomic::Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+/// Test that a tripped brake keeps answering requests and recording samples.
+async fn it_should_keep_the_brake_usable_after_a_trip() {
+    // The brake's own default trigger is a panic, which the layer does not use.
+    let ebrake = SharedEBrake::new(EBrake::new(2, 0));
+    let service = EBrakeLayer::new(ebrake.clone()).layer(service_fn(respond));
+    for path in ["/500", "/500", "/503"] {
+        let response = service.clone().oneshot(request(path)).await.unwrap();
+        assert!(response.status().is_server_error());
+    }
+    assert!(ebrake.should_trigger());
+
+    // A panic trigger fails the request that fired it, but leaves the brake usable.
+    let panicking = EBrakeLayer::with_trigger(ebrake.clone(), Trigger::Panic).layer(service_fn(respond));
+    let fired = tokio::spawn(panicking.oneshot(request("/500"))).await;
+    assert!(fired.unwrap_err().is_panic());
+    assert_eq!(ebrake.lock().current_failures(), 2);
+
+    for path in ["/200", "/200"] {
+        service.clone().oneshot(request(path)).await.unwrap();
+    }
+    assert!(!ebrake.should_trigger());
+    assert_eq!(ebrake.lock().current_successes(), 2);
+}
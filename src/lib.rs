@@ -7,6 +7,7 @@
 //! 
 //! This will use the sample and trigger functions separately.
 //! ```
+//! # #[cfg(feature = "std")] {
 //! use emergency_brake::*;
 //! let sample_window_size = 25;
 //! let failure_threshold = 3;
@@ -14,11 +15,13 @@
 //! for _ in 0..sample_window_size {
 //!    ebrake.add_sample(true);
 //! }
-//! assert_eq!(ebrake.trigger(&Trigger::Panic), false);
+//! assert_eq!(ebrake.trigger(&Trigger::Panic), TripOutcome::NotTripped);
+//! # }
 //! ```
 //! 
 //! This will use the trigger_on_sample function.
 //! ```
+//! # #[cfg(feature = "std")] {
 //! use emergency_brake::*;
 //! let sample_window_size = 25;
 //! let failure_threshold = 3;
@@ -26,29 +29,226 @@
 //! for _ in 0..sample_window_size {
 //!   ebrake.trigger_on_sample(true, &Trigger::Panic);
 //! }
-//! assert_eq!(ebrake.trigger(&Trigger::Panic), false);
+//! assert_eq!(ebrake.trigger(&Trigger::Panic), TripOutcome::NotTripped);
+//! # }
 //! ```
 //! 
+//! # `no_std`
+//! 
+//! Disabling the default `std` feature builds the crate without the standard
+//! library or an allocator. Only the fixed size [`EBrakeN`] is available, and
+//! the `Abort` and `Exit` triggers are removed, since both require
+//! `std::process`.
+//! 
+//! # Logging
+//! 
+//! Trip and sample events are emitted with `tracing`. For applications that
+//! use the `log` crate instead, the `log-backend` feature also emits each
+//! event as a `log` record, as long as no `tracing` subscriber has been set.
+//! 
+//! # WebAssembly
+//! 
+//! The core sample and trigger logic builds for `wasm32-unknown-unknown` with
+//! the `std`, `serde`, `metrics` and `persist` features. The
+//! `service_checker`, `service_checker_blocking`, `grpc_health` and `shutdown`
+//! features depend on native networking and are not supported there. That target has
+//! no process to terminate, so `Abort` and `Exit` trap the module; use
+//! `Panic`, `Log`, or a `Callback` that calls into JavaScript instead. It also
+//! has no monotonic clock by default, so the cooldown and time based windows
+//! need a custom [`Clock`].
+//! 
 //! 
 //! Kelsea Blackwell (c) 2023
 //! See LICENSE for licensing information.
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
 
-#[cfg(feature = "service_checker")]
-use async_trait::async_trait;
-
-
+use core::fmt;
+#[cfg(feature = "std")]
 use std::collections::VecDeque;
+#[cfg(feature = "std")]
 use std::process;
+#[cfg(feature = "std")]
+use std::time::Duration;
 
-#[cfg(feature = "service_checker")]
-use reqwest;
+#[cfg(feature = "std")]
+use tracing::{error, trace, Level};
+
+mod fixed;
+pub use fixed::EBrakeN;
+
+mod ewma;
+pub use ewma::EwmaBrake;
+
+#[cfg(feature = "std")]
+mod async_trigger;
+#[cfg(feature = "std")]
+pub use async_trigger::AsyncTrigger;
+
+#[cfg(feature = "std")]
+mod adaptive;
+#[cfg(feature = "std")]
+use adaptive::AdaptiveTolerance;
+#[cfg(feature = "std")]
+pub use adaptive::DEFAULT_ADAPTIVE_MIN_FAILURE_RATE;
+
+#[cfg(feature = "std")]
+mod arming;
+
+#[cfg(feature = "std")]
+mod atomic;
+#[cfg(feature = "std")]
+pub use atomic::AtomicEBrake;
+
+#[cfg(feature = "std")]
+mod bits;
+#[cfg(feature = "std")]
+use bits::BitRing;
+
+#[cfg(feature = "std")]
+mod burst;
+#[cfg(feature = "std")]
+pub use burst::BurstSampler;
+
+#[cfg(feature = "std")]
+mod builder;
+#[cfg(feature = "std")]
+pub use builder::EBrakeBuilder;
+
+#[cfg(feature = "std")]
+mod config;
+#[cfg(feature = "std")]
+pub use config::BrakeConfig;
+
+#[cfg(feature = "std")]
+mod registry;
+#[cfg(feature = "std")]
+pub use registry::BrakeRegistry;
+
+#[cfg(feature = "std")]
+mod shared;
+#[cfg(feature = "std")]
+pub use shared::SharedEBrake;
+
+#[cfg(feature = "std")]
+mod decay;
+#[cfg(feature = "std")]
+use decay::decayed_score;
+
+#[cfg(feature = "std")]
+mod hooks;
+
+#[cfg(feature = "std")]
+mod hysteresis;
+#[cfg(feature = "std")]
+use hooks::{RecoverHook, SnapshotHook};
+
+#[cfg(feature = "std")]
+mod predicate;
+#[cfg(feature = "std")]
+pub use predicate::TripPredicate;
+#[cfg(feature = "std")]
+use predicate::PredicateHook;
+
+#[cfg(feature = "std")]
+mod categories;
+#[cfg(feature = "std")]
+pub use categories::ErrorKind;
+#[cfg(feature = "std")]
+use categories::CategoryWindow;
+
+#[cfg(feature = "std")]
+mod clock;
+#[cfg(feature = "std")]
+pub use clock::{Clock, MockClock, SystemClock};
+#[cfg(feature = "std")]
+use clock::SharedClock;
+
+#[cfg(feature = "std")]
+mod merge;
+
+#[cfg(feature = "std")]
+mod trip_interval;
+
+#[cfg(feature = "std")]
+mod rate_limit;
+#[cfg(feature = "std")]
+use rate_limit::RateLimit;
+
+#[cfg(feature = "std")]
+mod events;
+#[cfg(feature = "std")]
+pub use events::{SampleEvent, SAMPLE_EVENT_CAPACITY};
+#[cfg(feature = "std")]
+use events::Subscribers;
+
+#[cfg(feature = "std")]
+mod report;
+#[cfg(feature = "std")]
+pub use report::CrashReport;
+
+#[cfg(feature = "std")]
+mod recent;
+
+#[cfg(feature = "std")]
+mod recovery;
+#[cfg(feature = "std")]
+pub use recovery::{BrakeState, RecoveryEstimate};
+
+#[cfg(feature = "std")]
+mod latch;
+
+#[cfg(feature = "std")]
+mod streak;
+
+#[cfg(feature = "std")]
+mod success_rate;
+
+#[cfg(feature = "std")]
+mod time_window;
+#[cfg(feature = "std")]
+pub use time_window::TimeWindowedEBrake;
+
+#[cfg(feature = "std")]
+mod typed;
+#[cfg(feature = "std")]
+pub use typed::{Outcome, TypedEBrake};
+
+#[cfg(feature = "metrics")]
+mod metrics;
+
+#[cfg(feature = "persist")]
+mod persist;
+
+#[cfg(feature = "timestamps")]
+mod timestamps;
+#[cfg(feature = "timestamps")]
+use timestamps::SampleTimes;
 
+#[cfg(feature = "shutdown")]
+mod shutdown;
+
+#[cfg(any(feature = "service_checker", feature = "service_checker_blocking"))]
+mod check_config;
+#[cfg(any(feature = "service_checker", feature = "service_checker_blocking"))]
+pub use check_config::{BodyMatcher, RedirectPolicy, ServiceCheckConfig, ServiceCheckError, DEFAULT_CHECK_TIMEOUT};
+
+#[cfg(feature = "service_checker")]
+mod service_checker;
 #[cfg(feature = "service_checker")]
-use tokio;
+pub use service_checker::{AggregationPolicy, RetryPolicy, ServiceChecker, WatchConfig, WatchHandle};
 
-use tracing::error;
+#[cfg(feature = "grpc_health")]
+mod grpc;
+
+#[cfg(feature = "service_checker_blocking")]
+mod blocking;
+
+#[cfg(feature = "tower")]
+mod layer;
+#[cfg(feature = "tower")]
+pub use layer::{EBrakeFuture, EBrakeLayer, EBrakeService};
 
 
 
@@ -63,14 +263,18 @@ pub trait EmergencyBrake {
 
     /// Returns true if the emergency brake should be triggered.
     /// Returns false if the emergency brake should not be triggered.
+    /// The emergency brake will not trigger until the sample window is full.
     fn should_trigger(&self) -> bool;
 
-    /// Returns false if the emergency brake has not been triggered.
-    /// If the emergency brake has been triggered, the process supplied trigger action will be executed.
-    fn trigger(&self, trigger: &'static Trigger) -> bool;
+    /// Returns `TripOutcome::NotTripped` if the emergency brake has not been triggered.
+    /// If the emergency brake has been triggered, the supplied trigger action will be executed.
+    /// Terminating actions never return; the others return `TripOutcome::Handled`.
+    fn trigger(&self, trigger: &Trigger) -> TripOutcome;
 
     /// Returns false if the emergency brake has not been triggered.
     /// If the emergency brake has been triggered, the process will be aborted.
+    /// Only available with the `std` feature.
+    #[cfg(feature = "std")]
     fn trigger_abort(&self) -> bool;
 
     /// Returns false if the emergency brake has not been triggered.
@@ -78,97 +282,437 @@ pub trait EmergencyBrake {
     fn trigger_panic(&self) -> bool;
 
     /// Insert a sample and check if the emergency brake should be triggered.
-    fn trigger_on_sample(&mut self, sample: bool, trigger: &'static Trigger) -> bool;
-}
+    fn trigger_on_sample(&mut self, sample: bool, trigger: &Trigger) -> TripOutcome;
 
+    /// Similar to trigger, but takes the trigger action by value, so an action
+    /// built inline, such as from configuration, need not be kept alive.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrakeN::<1>::new(0);
+    /// ebrake.add_sample(false);
+    /// assert_eq!(ebrake.trigger_with(Trigger::Log), TripOutcome::Handled);
+    /// ```
+    fn trigger_with(&self, trigger: Trigger) -> TripOutcome {
+        self.trigger(&trigger)
+    }
 
-/// The ServiceCheck trait is the interface for checking or monitoring a service.
-#[cfg_attr(docsrs, doc(cfg(feature = "service_checker")))]
-#[cfg(feature = "service_checker")]
-#[async_trait]
-pub trait ServiceChecker {
-    /// Check if the service is running. This takes a URI as a parameter, and
-    /// performs a basic HTTP GET request to the URI. If the request is successful,
-    /// it will return true and assume the service is running, false otherwise.
-    async fn check_service_endpoint(&self, uri: &str) -> bool;
+    /// Similar to trigger_on_sample, but takes the trigger action by value.
+    fn trigger_on_sample_with(&mut self, sample: bool, trigger: Trigger) -> TripOutcome {
+        self.trigger_on_sample(sample, &trigger)
+    }
 
-    /// Similar to check_service_endpoint, but will check the service at a given
-    /// interval. This will spawn a background thread and consume the current
-    /// instance of the EBrake. If the service stops responding, the EBrake will
-    /// be triggered and the process will be aborted.
-    async fn watch_service_endpoint(mut self, uri: &'static str, interval: usize, trigger: &'static Trigger);
+    /// Clear the sample window and all failure and success counts.
+    /// The window size and tolerance are preserved.
+    fn reset(&mut self);
 }
 
+
 /// The Trigger enum defines the action to take when the emergency brake is triggered.
-#[derive(Clone, Debug, PartialEq)]
+/// Every action is a plain value, so triggers are `Copy`.
+/// With the `serde` feature, `Callback` and `All` triggers are skipped, since a
+/// function pointer or a static slice cannot be persisted.
+/// Without the `std` feature, the `Abort` and `Exit` triggers are unavailable.
+/// The `Signal` trigger is only available on Unix platforms.
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Trigger {
     /// Abort the process.
+    #[cfg(feature = "std")]
     Abort,
 
     /// Panic the process.
+    #[default]
     Panic,
+
+    /// Invoke the supplied function instead of terminating the process.
+    /// This allows the application to flush logs, close connections or notify
+    /// a supervisor before shutting itself down.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Callback(fn()),
+
+    /// Exit the process with the given exit code.
+    /// Unlike `Abort`, this terminates cleanly via `std::process::exit`, so no
+    /// core dump is produced. Destructors are only run insofar as
+    /// `process::exit` runs them: the current stack is not unwound.
+    #[cfg(feature = "std")]
+    Exit(i32),
+
+    /// Send the given signal to the current process, such as `libc::SIGTERM`
+    /// for services whose supervisor expects a signal driven graceful shutdown.
+    /// Once a signal handler returns, the trigger returns `TripOutcome::Handled`.
+    /// Only available on Unix platforms, with the `std` feature.
+    #[cfg(all(feature = "std", unix))]
+    Signal(i32),
+
+    /// Run each of the given actions in order, such as `Log` and then `Abort`.
+    /// A terminating action ends the sequence. The actions are borrowed from
+    /// a static slice, so the trigger stays `Copy`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    All(&'static [Trigger]),
+
+    /// Take no action and report the trip to the caller, which decides how to
+    /// respond. Nothing is terminated, panicked or signalled, so this is the
+    /// safe choice for libraries that must leave that decision to their host.
+    Return,
+
+    /// Log the trigger event without terminating the process.
+    /// This is a dry run mode, useful for tuning thresholds before enabling
+    /// a terminal trigger.
+    Log,
+}
+
+impl Trigger {
+    /// Performs the trigger action. Returns `TripOutcome::Handled` for
+    /// actions that do not terminate the process.
+    fn execute(&self) -> TripOutcome {
+        match self {
+            #[cfg(feature = "std")]
+            Trigger::Abort => process::abort(),
+            Trigger::Panic => panic!("Emergency brake triggered!"),
+            Trigger::Callback(callback) => {
+                callback();
+                TripOutcome::Handled
+            },
+            #[cfg(feature = "std")]
+            Trigger::Exit(code) => process::exit(*code),
+            #[cfg(all(feature = "std", unix))]
+            Trigger::Signal(signal) => {
+                // SAFETY: raise has no preconditions, and reports an invalid signal by returning -1.
+                if unsafe { libc::raise(*signal) } != 0 {
+                    error!(signal, "Emergency brake failed to raise signal!");
+                }
+                TripOutcome::Handled
+            },
+            Trigger::All(triggers) => {
+                for trigger in triggers.iter() {
+                    trigger.execute();
+                }
+                TripOutcome::Handled
+            },
+            Trigger::Return | Trigger::Log => TripOutcome::Handled,
+        }
+    }
+}
+
+/// The TripOutcome enum describes the result of checking an emergency brake
+/// with a trigger action. Terminating actions such as `Abort`, `Exit` and
+/// `Panic` never produce an outcome, since the process or thread does not
+/// return from them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TripOutcome {
+    /// The emergency brake was not triggered, so no action was taken.
+    NotTripped,
+
+    /// The emergency brake was triggered and a non-terminating action, such
+    /// as `Return`, `Log` or `Callback`, was executed.
+    Handled,
+}
+
+impl TripOutcome {
+    /// Returns true if the emergency brake was triggered.
+    pub fn is_tripped(&self) -> bool {
+        matches!(self, TripOutcome::Handled)
+    }
+}
+
+impl PartialEq for Trigger {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            #[cfg(feature = "std")]
+            (Trigger::Abort, Trigger::Abort) => true,
+            (Trigger::Panic, Trigger::Panic) => true,
+            (Trigger::Callback(a), Trigger::Callback(b)) => core::ptr::fn_addr_eq(*a, *b),
+            #[cfg(feature = "std")]
+            (Trigger::Exit(a), Trigger::Exit(b)) => a == b,
+            #[cfg(all(feature = "std", unix))]
+            (Trigger::Signal(a), Trigger::Signal(b)) => a == b,
+            (Trigger::All(a), Trigger::All(b)) => a == b,
+            (Trigger::Return, Trigger::Return) => true,
+            (Trigger::Log, Trigger::Log) => true,
+            _ => false,
+        }
+    }
+}
+
+/// The EBrakeError enum describes an invalid emergency brake configuration.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EBrakeError {
+    /// The sample window has a size of zero.
+    ZeroWindow,
+
+    /// The tolerance is greater than or equal to the window size, so the
+    /// emergency brake could never be triggered.
+    ToleranceTooLarge,
+
+    /// The failure rate is not within `(0.0, 1.0]`.
+    InvalidFailureRate,
+
+    /// The minimum number of samples is greater than the window size, so the
+    /// emergency brake could never be triggered.
+    MinSamplesTooLarge,
+
+    /// The decay factor is not within `(0.0, 1.0]`.
+    InvalidDecay,
+
+    /// The minimum success rate is not within `(0.0, 1.0]`.
+    InvalidSuccessRate,
+
+    /// The adaptive tolerance has no calibration samples, a margin that is
+    /// not a positive number, or a minimum failure rate outside `[0.0, 1.0]`.
+    InvalidCalibration,
+
+    /// The EWMA smoothing factor is not within `(0.0, 1.0]`.
+    InvalidAlpha,
 }
 
+impl fmt::Display for EBrakeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EBrakeError::ZeroWindow => write!(f, "sample window size must be greater than zero"),
+            EBrakeError::ToleranceTooLarge => write!(f, "tolerance must be less than the sample window size"),
+            EBrakeError::InvalidFailureRate => write!(f, "failure rate must be greater than 0.0 and at most 1.0"),
+            EBrakeError::MinSamplesTooLarge => write!(f, "minimum samples must not exceed the sample window size"),
+            EBrakeError::InvalidDecay => write!(f, "decay must be greater than 0.0 and at most 1.0"),
+            EBrakeError::InvalidSuccessRate => write!(f, "success rate must be greater than 0.0 and at most 1.0"),
+            EBrakeError::InvalidCalibration => write!(f, "calibration samples and margin must be greater than zero"),
+            EBrakeError::InvalidAlpha => write!(f, "alpha must be greater than 0.0 and at most 1.0"),
+        }
+    }
+}
+
+impl core::error::Error for EBrakeError {}
+
 /// The emergency brake is a circular queue of boolean samples with a defined size and tolerance.
+/// Samples are bit-packed, so each one occupies a single bit of memory.
+/// A window of zero samples, as created by `EBrake::default()`, never stores
+/// samples and never triggers; use `EBrake::try_new` to reject it instead.
+/// With the `serde` feature, the sample window and counters can be persisted.
+///
+/// # Partial windows
+///
+/// Until the window holds `min_samples` samples, which defaults to the full
+/// window size, only the checks that do not depend on the window trip the brake:
+///
+/// | Check                                   | Waits for `min_samples` |
+/// |-----------------------------------------|-------------------------|
+/// | Failure count or weight (`tolerance`)   | Yes                     |
+/// | Failure rate (`new_with_rate`)          | Yes                     |
+/// | Decayed failure score (`set_decay`)     | Yes                     |
+/// | Adaptive tolerance                      | Yes                     |
+/// | Minimum success rate                    | Yes                     |
+/// | Failure streak (`set_max_streak`)       | No                      |
+///
+/// The standalone queries `should_trigger_streak` and `should_trigger_recent`
+/// never wait. Every trigger method, `would_trigger` and `snapshot` apply the
+/// same rule.
+#[cfg(feature = "std")]
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+// The derived impls are generated as inherent functions, so that the
+// `Deserialize` impl below can validate the restored window.
+#[cfg_attr(feature = "serde", serde(remote = "Self"))]
 pub struct EBrake {
-    data: VecDeque<bool>,
+    adaptive: Option<AdaptiveTolerance>,
+    #[cfg(feature = "service_checker_blocking")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    blocking_client: std::sync::OnceLock<reqwest::blocking::Client>,
+    categories: Option<CategoryWindow>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    clock: SharedClock,
+    cooldown: Option<Duration>,
+    data: BitRing,
+    decay: Option<f64>,
+    default_trigger: Trigger,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    crash_report: Option<CrashReport>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    disarmed: bool,
+    failure_rate_threshold: Option<f64>,
+    failure_streak: usize,
+    failure_weight: usize,
     failures: usize,
+    half_open_max_probes: Option<usize>,
+    #[cfg(feature = "service_checker")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    http_client: std::sync::OnceLock<reqwest::Client>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    last_trip_at: Option<std::time::Instant>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    last_tripped: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    latched: bool,
+    latching: bool,
+    #[cfg_attr(feature = "serde", serde(default, with = "level_name"))]
+    log_level: Option<Level>,
+    #[cfg(feature = "metrics")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    metrics: Option<metrics::BrakeMetrics>,
+    max_streak: Option<usize>,
+    min_samples: Option<usize>,
+    min_success_rate: Option<f64>,
+    min_trip_interval: Option<Duration>,
+    name: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    on_recover: Option<RecoverHook>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    on_success: Option<SnapshotHook>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    on_trigger: Option<SnapshotHook>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    probes: usize,
+    reset_failure_rate: Option<f64>,
     samples: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    state: BrakeState,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    subscribers: Subscribers,
     successes: usize,
+    #[cfg(feature = "timestamps")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    times: SampleTimes,
     tolerance: usize,
+    total_samples: u64,
+    total_trips: u64,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    trigger_limit: Option<RateLimit>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    trip_predicate: Option<PredicateHook>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    tripped: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    warmed_up: bool,
+    weights: Option<VecDeque<usize>>,
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for EBrake {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        EBrake::serialize(self, serializer)
+    }
+}
 
-impl Default for Trigger {
-    fn default() -> Self {
-        Trigger::Panic
+/// A restored brake is validated before it is returned, since counters that
+/// disagree with the window would make it panic or grow without bound.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for EBrake {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut ebrake = EBrake::deserialize(deserializer)?;
+        ebrake.check_window().map_err(serde::de::Error::custom)?;
+        ebrake.refresh_warm_up();
+        Ok(ebrake)
     }
 }
 
-impl EmergencyBrake for EBrake {
-    fn add_sample(&mut self, sample: bool) {
-        if self.data.len() == self.samples {
-            match self.data.pop_front() {
-                Some(true) => self.successes -= 1,
-                Some(false) => self.failures -= 1,
-                None => {},
+/// Serializes a log level by its name, such as `"WARN"`.
+#[cfg(feature = "serde")]
+mod level_name {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use tracing::Level;
+
+    pub(crate) fn serialize<S: Serializer>(level: &Option<Level>, serializer: S) -> Result<S::Ok, S::Error> {
+        level.map(|level| level.as_str()).serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Level>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|name| name.parse().map_err(serde::de::Error::custom))
+            .transpose()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl EBrake {
+    /// Returns an error describing the first counter or buffer that
+    /// disagrees with the sample window.
+    fn check_window(&self) -> Result<(), &'static str> {
+        let len = self.data.len();
+        if len > self.samples {
+            return Err("the sample window holds more samples than its size");
+        }
+        if self.failures.checked_add(self.successes) != Some(len) {
+            return Err("the failure and success counts do not add up to the window length");
+        }
+        if self.data.iter().filter(|sample| !sample).count() != self.failures {
+            return Err("the failure count does not match the failures in the window");
+        }
+        if let Some(weights) = &self.weights {
+            if weights.len() != len {
+                return Err("the sample weights do not match the window length");
+            }
+            let failure_weight = self.data.iter().zip(weights).filter(|(sample, _)| !sample).fold(0usize, |sum, (_, weight)| sum.saturating_add(*weight));
+            if self.failure_weight > failure_weight {
+                return Err("the failure weight exceeds the weights of the failures in the window");
             }
+        } else if self.failure_weight > self.failures {
+            return Err("the failure weight exceeds the failures in the window");
         }
-        
-        match sample {
-            true => self.successes += 1,
-            false => self.failures += 1,
+        if self.categories.as_ref().is_some_and(|categories| !categories.matches_window(&self.data)) {
+            return Err("the failure categories do not match the window");
         }
+        Ok(())
+    }
+}
 
-        self.data.push_back(sample);
+/// The BrakeSnapshot is a point in time view of an emergency brake's window.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BrakeSnapshot {
+    /// The configured number of samples in the window.
+    pub window_size: usize,
+
+    /// The number of samples currently held in the window.
+    pub filled: usize,
+
+    /// The number of failures in the window.
+    pub failures: usize,
+
+    /// The number of successes in the window.
+    pub successes: usize,
+
+    /// The fraction of samples in the window that are failures.
+    pub failure_rate: f64,
+
+    /// Whether the emergency brake should be triggered.
+    pub tripped: bool,
+
+    /// The number of samples recorded over the brake's lifetime.
+    pub total_samples: u64,
+
+    /// The number of times the brake has tripped over its lifetime.
+    pub total_trips: u64,
+}
+
+#[cfg(feature = "std")]
+impl EmergencyBrake for EBrake {
+    fn add_sample(&mut self, sample: bool) {
+        self.push_sample(sample, 1, None);
     }
 
     fn should_trigger(&self) -> bool {
-        if self.data.len() < self.tolerance {
-            return false;
-        }
-
-        self.failures > self.tolerance
+        let tripped = self.latched
+            || match self.cooldown {
+                Some(_) => self.tripped,
+                None => self.window_tripped(),
+            };
+        tripped && !self.within_trip_interval()
     }
 
-    fn trigger(&self, trigger: &'static Trigger) -> bool {
-        match self.should_trigger() {
+    fn trigger(&self, trigger: &Trigger) -> TripOutcome {
+        match self.should_fire() {
             true => {
-                error!("Emergency brake triggered!");
-                match trigger {
-                    Trigger::Abort => process::abort(),
-                    Trigger::Panic => panic!("Emergency brake triggered!"),
-                }
+                self.log_trip("Emergency brake triggered!");
+                self.notify_trip();
+                trigger.execute()
             },
-            false => false,
+            false => TripOutcome::NotTripped,
         }
     }
 
     fn trigger_abort(&self) -> bool {
-        match self.should_trigger() {
+        match self.should_fire() {
             true => {
-                error!("Emergency brake abort triggered!");
+                self.log_trip("Emergency brake abort triggered!");
+                self.notify_trip();
                 process::abort();
             },
             false => false,
@@ -176,67 +720,791 @@ impl EmergencyBrake for EBrake {
     }
 
     fn trigger_panic(&self) -> bool {
-        match self.should_trigger() {
+        match self.should_fire() {
             true => {
-                error!("Emergency brake panic triggered!");
+                self.log_trip("Emergency brake panic triggered!");
+                self.notify_trip();
                 panic!("Emergency brake panic triggered!");
             },
             false => false,
         }
     }
 
-    fn trigger_on_sample(&mut self, sample: bool, trigger: &'static Trigger) -> bool {
+    fn trigger_on_sample(&mut self, sample: bool, trigger: &Trigger) -> TripOutcome {
         self.add_sample(sample);
         self.trigger(trigger)
     }
-}
-
 
-
-#[cfg(feature = "service_checker")]
-#[async_trait]
-impl ServiceChecker for EBrake {
-    async fn check_service_endpoint(&self, uri: &str) -> bool {
-        let client = reqwest::Client::new();
-        let response = client.get(uri).send().await;
-        match response {
-            Ok(_) => true,
-            Err(_) => false,
-        }
-    }
-
-    async fn watch_service_endpoint(mut self, uri: &'static str, interval: usize, trigger: &'static Trigger) {
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(interval as u64));
-            loop {
-                interval.tick().await;
-                let result = self.check_service_endpoint(uri).await;
-                self.trigger_on_sample(result, trigger);
-            }
-        });
+    fn reset(&mut self) {
+        self.clear_window();
+        self.last_tripped = false;
+        #[cfg(feature = "timestamps")]
+        self.times.clear();
+        if let Some(limit) = &self.trigger_limit {
+            limit.clear();
+        }
+        self.latched = false;
+        self.probes = 0;
+        self.state = BrakeState::Closed;
+        self.tripped = false;
     }
 }
 
 
+
+#[cfg(feature = "std")]
 impl EBrake {
     /// Creates a new Emergency Brake with the given number of samples and tolerance.
+    /// With zero samples, the brake never stores samples and never triggers.
     /// ```
     /// use emergency_brake::EBrake;
     /// let ebrake = EBrake::new(10, 3);
     /// ```
     pub fn new(samples: usize, tolerance: usize) -> Self {
         EBrake {
-            data: VecDeque::with_capacity(samples),
+            adaptive: None,
+            #[cfg(feature = "service_checker_blocking")]
+            blocking_client: std::sync::OnceLock::new(),
+            categories: None,
+            clock: SharedClock::default(),
+            cooldown: None,
+            crash_report: None,
+            data: BitRing::with_capacity(samples),
+            decay: None,
+            default_trigger: Trigger::default(),
+            disarmed: false,
+            failure_rate_threshold: None,
+            failure_streak: 0,
+            failure_weight: 0,
             failures: 0,
-            samples: samples,
+            half_open_max_probes: None,
+            #[cfg(feature = "service_checker")]
+            http_client: std::sync::OnceLock::new(),
+            last_trip_at: None,
+            last_tripped: false,
+            latched: false,
+            latching: false,
+            log_level: None,
+            #[cfg(feature = "metrics")]
+            metrics: None,
+            max_streak: None,
+            min_samples: None,
+            min_success_rate: None,
+            min_trip_interval: None,
+            name: None,
+            on_recover: None,
+            on_success: None,
+            on_trigger: None,
+            probes: 0,
+            reset_failure_rate: None,
+            samples,
+            state: BrakeState::Closed,
+            subscribers: Subscribers::default(),
             successes: 0,
-            tolerance: tolerance,
+            #[cfg(feature = "timestamps")]
+            times: SampleTimes::default(),
+            tolerance,
+            total_samples: 0,
+            total_trips: 0,
+            trigger_limit: None,
+            trip_predicate: None,
+            warmed_up: false,
+            tripped: false,
+            weights: None,
+        }
+    }
+
+    /// Creates a new Emergency Brake, validating that the window is not empty
+    /// and that the tolerance is less than the number of samples.
+    /// ```
+    /// use emergency_brake::*;
+    /// assert!(EBrake::try_new(10, 3).is_ok());
+    /// assert_eq!(EBrake::try_new(3, 3).unwrap_err(), EBrakeError::ToleranceTooLarge);
+    /// ```
+    pub fn try_new(samples: usize, tolerance: usize) -> Result<Self, EBrakeError> {
+        if samples == 0 {
+            return Err(EBrakeError::ZeroWindow);
+        }
+
+        if tolerance >= samples {
+            return Err(EBrakeError::ToleranceTooLarge);
+        }
+
+        Ok(EBrake::new(samples, tolerance))
+    }
+
+    /// Creates a new Emergency Brake that triggers once the fraction of failures
+    /// in a full window reaches the given rate, rather than an absolute count.
+    /// The rate must be within `(0.0, 1.0]`.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new_with_rate(10, 0.2).unwrap();
+    /// for i in 0..10 {
+    ///     ebrake.add_sample(i >= 2);
+    /// }
+    /// assert!(ebrake.should_trigger());
+    /// ```
+    pub fn new_with_rate(samples: usize, failure_rate: f64) -> Result<Self, EBrakeError> {
+        if samples == 0 {
+            return Err(EBrakeError::ZeroWindow);
+        }
+
+        if !(failure_rate > 0.0 && failure_rate <= 1.0) {
+            return Err(EBrakeError::InvalidFailureRate);
+        }
+
+        let mut ebrake = EBrake::new(samples, 0);
+        ebrake.failure_rate_threshold = Some(failure_rate);
+        Ok(ebrake)
+    }
+
+    /// Returns true if the window is full and its failures cross the threshold.
+    fn window_tripped(&self) -> bool {
+        debug_assert_eq!(self.warmed_up, self.has_min_samples(self.data.len()), "stale warm-up flag");
+        let decayed = self.decay.map(|decay| decayed_score(self.weighted_newest_first(), decay));
+        let (len, failures) = (self.data.len(), self.failures);
+        let tripped = self.rules_tripped(self.adaptive.as_ref(), self.warmed_up, len, failures, self.failure_weight, self.failure_streak, decayed);
+        self.apply_trip_predicate(len, failures, tripped)
+    }
+
+    /// Recomputes the cached warm-up flag after the window shrinks or its
+    /// minimum changes. Filling the window only ever sets the flag, so
+    /// `push_sample` sets it directly, and only until it is set.
+    pub(crate) fn refresh_warm_up(&mut self) {
+        self.warmed_up = self.has_min_samples(self.data.len());
+    }
+
+    /// Returns true if a window holding `len` samples may be judged by the
+    /// windowed thresholds. This is the single rule for partial windows.
+    fn has_min_samples(&self, len: usize) -> bool {
+        len > 0 && len >= self.min_samples()
+    }
+
+    /// Returns true if a window with the given statistics would cross the
+    /// threshold, as decided by the trip predicate when one is set.
+    /// `decayed` is the decayed failure score, when a decay is configured.
+    fn tripped_with(&self, len: usize, failures: usize, failure_weight: usize, streak: usize, decayed: Option<f64>) -> bool {
+        let tripped = self.rules_tripped(self.adaptive.as_ref(), self.has_min_samples(len), len, failures, failure_weight, streak, decayed);
+        self.apply_trip_predicate(len, failures, tripped)
+    }
+
+    /// Returns true if a window with the given statistics crosses one of the
+    /// built-in thresholds, judging an adaptive threshold with `adaptive`.
+    /// `warmed_up` tells whether the window holds `min_samples` samples.
+    #[allow(clippy::too_many_arguments)]
+    fn rules_tripped(&self, adaptive: Option<&AdaptiveTolerance>, warmed_up: bool, len: usize, failures: usize, failure_weight: usize, streak: usize, decayed: Option<f64>) -> bool {
+        if self.samples == 0 {
+            return false;
+        }
+        if self.max_streak.is_some_and(|max_streak| streak >= max_streak) {
+            return true;
+        }
+        if !warmed_up {
+            return false;
         }
+        if self.min_success_rate.is_some_and(|rate| ((len - failures) as f64 / len as f64) < rate) {
+            return true;
+        }
+
+        match (self.failure_rate_threshold, adaptive, decayed) {
+            (Some(rate), _, _) => self.rate_exceeded(rate, failures as f64 / len as f64),
+            (None, Some(adaptive), _) => adaptive.exceeded(failures, len),
+            (None, None, Some(score)) => score > self.tolerance as f64,
+            (None, None, None) => failure_weight > self.tolerance,
+        }
+    }
+
+    /// Returns true if adding `next_sample` would trigger the emergency brake,
+    /// without changing the brake. This accounts for the sample that would be
+    /// evicted from a full window, and treats `next_sample` as having a weight of one.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(3, 1);
+    /// ebrake.add_samples(&[true, false, true]);
+    /// assert!(ebrake.would_trigger(false));
+    /// assert!(!ebrake.would_trigger(true));
+    /// assert!(!ebrake.should_trigger());
+    /// ```
+    pub fn would_trigger(&self, next_sample: bool) -> bool {
+        let (mut len, mut failures, mut failure_weight) = (self.data.len(), self.failures, self.failure_weight);
+        if len == self.samples && len > 0 {
+            if self.data.front() == Some(false) {
+                failures = failures.saturating_sub(1);
+                let weight = self.weights.as_ref().and_then(|weights| weights.front().copied()).unwrap_or(1);
+                failure_weight = failure_weight.saturating_sub(weight);
+            }
+            len -= 1;
+        }
+        len += 1;
+        let streak = match next_sample {
+            true => 0,
+            false => {
+                failures = failures.saturating_add(1);
+                failure_weight = failure_weight.saturating_add(1);
+                self.failure_streak.saturating_add(1)
+            },
+        };
+
+        let decayed = self.decay.map(|decay| {
+            let kept = self.weighted_newest_first().take(len - 1);
+            decayed_score(std::iter::once((next_sample, 1)).chain(kept), decay)
+        });
+        // The sample may complete the calibration of an adaptive tolerance.
+        let adaptive = self.adaptive.clone().map(|mut adaptive| {
+            adaptive.observe(next_sample);
+            adaptive
+        });
+        let rules_tripped = self.rules_tripped(adaptive.as_ref(), self.has_min_samples(len), len, failures, failure_weight, streak, decayed);
+        let window_tripped = self.apply_trip_predicate(len, failures, rules_tripped);
+        // A latched brake stays tripped, and a latching one latches on this trip.
+        let latched = self.latched || (self.latching && window_tripped);
+        let tripped = latched
+            || match (self.cooldown, self.state()) {
+                (None, _) | (Some(_), BrakeState::Closed) => window_tripped,
+                (Some(_), BrakeState::HalfOpen) => !next_sample,
+                (Some(_), BrakeState::Open { .. }) => false,
+            };
+        tripped && !self.within_trip_interval()
+    }
+
+    /// Emits the trip event at the configured level, labelled with the brake's
+    /// name and window statistics.
+    pub(crate) fn log_trip(&self, message: &'static str) {
+        let name = self.name.as_deref();
+        let (failures, samples) = (self.failures, self.samples);
+        match self.log_level() {
+            Level::ERROR => error!(name, failures, samples, "{}", message),
+            Level::WARN => tracing::warn!(name, failures, samples, "{}", message),
+            Level::INFO => tracing::info!(name, failures, samples, "{}", message),
+            Level::DEBUG => tracing::debug!(name, failures, samples, "{}", message),
+            _ => trace!(name, failures, samples, "{}", message),
+        }
+    }
+
+    /// Inserts a sample with the given failure weight and category, evicting
+    /// the oldest sample if the window is full.
+    fn push_sample(&mut self, sample: bool, weight: usize, category: Option<ErrorKind>) {
+        // A zero-size window never stores samples, so it can never trip.
+        if self.samples == 0 {
+            self.total_samples = self.total_samples.saturating_add(1);
+            return;
+        }
+
+        let was_tripped = self.on_recover.is_some() && self.in_trip();
+        if self.data.len() == self.samples {
+            self.evict_oldest();
+        }
+
+        // Weights are only tracked once a sample with a weight other than one is added.
+        if weight != 1 && self.weights.is_none() {
+            self.weights = Some(VecDeque::from(vec![1; self.data.len()]));
+        }
+        if let Some(weights) = &mut self.weights {
+            weights.push_back(weight);
+        }
+
+        // Likewise, categories are only tracked once a categorized failure is added.
+        if category.is_some() && self.categories.is_none() {
+            self.categories = Some(CategoryWindow::with_len(self.data.len()));
+        }
+        if let Some(categories) = &mut self.categories {
+            categories.push_back(category);
+        }
+
+        // The counters saturate rather than wrap, so a long-running brake with heavy
+        // weights or an endless failure streak degrades gracefully instead of panicking.
+        match sample {
+            true => {
+                self.successes = self.successes.saturating_add(1);
+                self.failure_streak = 0;
+            },
+            false => {
+                self.failures = self.failures.saturating_add(1);
+                self.failure_streak = self.failure_streak.saturating_add(1);
+                self.failure_weight = self.failure_weight.saturating_add(weight);
+            },
+        }
+
+        self.data.push_back(sample);
+        if !self.warmed_up {
+            self.warmed_up = self.has_min_samples(self.data.len());
+        }
+        if let Some(adaptive) = &mut self.adaptive {
+            adaptive.observe(sample);
+        }
+        #[cfg(feature = "timestamps")]
+        self.times.push_back(self.data.len(), self.clock.now());
+        // The event is filtered at its callsite, so this is cheap when tracing is disabled.
+        trace!(sample, failures = self.failures, successes = self.successes, "Emergency brake sample recorded");
+        #[cfg(feature = "metrics")]
+        self.record_window_metrics();
+        self.update_state(sample);
+        if was_tripped && !self.in_trip() {
+            self.notify_recover();
+        }
+        self.count_sample();
+        self.subscribers.publish(|| SampleEvent {
+            timestamp: self.clock.now(),
+            sample,
+            failures: self.failures,
+        });
+        if sample {
+            self.notify_success();
+        }
+    }
+
+    /// Updates the lifetime totals after a sample, counting a trip on each rising edge.
+    fn count_sample(&mut self) {
+        self.total_samples = self.total_samples.saturating_add(1);
+        let tripped = self.should_trigger();
+        if tripped && !self.last_tripped {
+            self.total_trips = self.total_trips.saturating_add(1);
+            #[cfg(feature = "metrics")]
+            self.record_trip_metrics();
+            self.last_trip_at = Some(self.clock.now());
+            #[cfg(feature = "timestamps")]
+            self.record_trip_time();
+            // Each new trip fires immediately, regardless of the rate limit.
+            if let Some(limit) = &self.trigger_limit {
+                limit.clear();
+            }
+        }
+        self.last_tripped = tripped;
+    }
+
+    /// Removes the oldest sample from the window, updating the counters.
+    fn evict_oldest(&mut self) -> Option<bool> {
+        let sample = self.data.pop_front()?;
+        let weight = self.weights.as_mut().and_then(VecDeque::pop_front).unwrap_or(1);
+        if let Some(categories) = &mut self.categories {
+            categories.pop_front();
+        }
+        #[cfg(feature = "timestamps")]
+        self.times.pop_front();
+        match sample {
+            true => {
+                debug_assert!(self.successes > 0, "success count underflow on eviction");
+                self.successes = self.successes.saturating_sub(1);
+            },
+            false => {
+                debug_assert!(self.failures > 0, "failure count underflow on eviction");
+                self.failures = self.failures.saturating_sub(1);
+                self.failure_weight = self.failure_weight.saturating_sub(weight);
+            },
+        }
+        // The warm-up flag is left to the caller: a full window refills the
+        // slot straight away, and shrinking the window refreshes it.
+        Some(sample)
+    }
+
+    /// Empties the window and zeroes its counters.
+    fn clear_window(&mut self) {
+        self.data.clear();
+        self.failure_streak = 0;
+        self.failure_weight = 0;
+        self.failures = 0;
+        self.successes = 0;
+        self.weights = None;
+        self.categories = None;
+        self.warmed_up = false;
+        #[cfg(feature = "timestamps")]
+        self.times.clear_window();
+        #[cfg(feature = "metrics")]
+        self.record_window_metrics();
+    }
+
+    /// Returns a builder for configuring a new Emergency Brake.
+    pub fn builder() -> EBrakeBuilder {
+        EBrakeBuilder::new()
+    }
+
+    /// Returns `TripOutcome::NotTripped` if the emergency brake has not been triggered.
+    /// If the emergency brake has been triggered, the default trigger action will be executed.
+    pub fn trigger_default(&self) -> TripOutcome {
+        self.trigger(&self.default_trigger)
+    }
+
+    /// Adds a sample to the window and then executes the default trigger action
+    /// if the emergency brake has been triggered.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::builder().window_size(2).default_trigger(Trigger::Log).build().unwrap();
+    /// assert_eq!(ebrake.trigger_on_sample_default(false), TripOutcome::NotTripped);
+    /// assert_eq!(ebrake.trigger_on_sample_default(false), TripOutcome::Handled);
+    /// ```
+    pub fn trigger_on_sample_default(&mut self, sample: bool) -> TripOutcome {
+        let trigger = self.default_trigger;
+        self.trigger_on_sample(sample, &trigger)
+    }
+
+    /// Returns the trigger action used by `trigger_default`.
+    pub fn default_trigger(&self) -> &Trigger {
+        &self.default_trigger
+    }
+
+    /// Replaces the trigger action used by `trigger_default` and
+    /// `trigger_on_sample_default`, so the default action can be escalated
+    /// at runtime, for example from `Trigger::Log` to `Trigger::Abort` once
+    /// the brake has proven itself.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::builder().window_size(1).default_trigger(Trigger::Log).build().unwrap();
+    /// ebrake.set_default_trigger(Trigger::Abort);
+    /// assert_eq!(ebrake.default_trigger(), &Trigger::Abort);
+    /// ```
+    pub fn set_default_trigger(&mut self, trigger: Trigger) {
+        self.default_trigger = trigger;
+    }
+
+    /// Returns the fraction of samples in the current window that are failures.
+    /// An empty window has a failure rate of `0.0`.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(10, 3);
+    /// ebrake.add_sample(true);
+    /// ebrake.add_sample(false);
+    /// assert_eq!(ebrake.failure_rate(), 0.5);
+    /// ```
+    pub fn failure_rate(&self) -> f64 {
+        if self.data.is_empty() {
+            return 0.0;
+        }
+
+        self.failures as f64 / self.data.len() as f64
+    }
+
+    /// Returns the number of samples recorded over the brake's lifetime.
+    /// Unlike the window counts, this keeps growing as samples age out and is
+    /// preserved by `reset`.
+    pub fn total_samples(&self) -> u64 {
+        self.total_samples
+    }
+
+    /// Returns the number of times the brake has tripped over its lifetime.
+    /// A trip is counted each time `should_trigger` goes from false to true
+    /// after a sample is recorded.
+    pub fn total_trips(&self) -> u64 {
+        self.total_trips
+    }
+
+    /// Returns the number of failures in the current window.
+    pub fn current_failures(&self) -> usize {
+        self.failures
+    }
+
+    /// Returns the number of successes in the current window.
+    pub fn current_successes(&self) -> usize {
+        self.successes
+    }
+
+    /// Insert a batch of samples into the emergency brake, oldest first.
+    /// If the batch is longer than the window, only the last window-sized
+    /// part of the batch remains.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(3, 1);
+    /// ebrake.add_samples(&[false, false, true, true, false]);
+    /// assert_eq!(ebrake.current_failures(), 1);
+    /// ```
+    pub fn add_samples(&mut self, samples: &[bool]) {
+        if self.samples == 0 {
+            self.total_samples = self.total_samples.saturating_add(samples.len() as u64);
+            return;
+        }
+
+        let (skipped, kept) = samples.split_at(samples.len().saturating_sub(self.samples));
+        if let Some(adaptive) = &mut self.adaptive {
+            skipped.iter().for_each(|sample| adaptive.observe(*sample));
+        }
+        // Skipped samples still extend or break the failure streak.
+        match skipped.iter().rposition(|sample| *sample) {
+            Some(last_success) => self.failure_streak = skipped.len() - last_success - 1,
+            None => self.failure_streak = self.failure_streak.saturating_add(skipped.len()),
+        }
+        self.total_samples = self.total_samples.saturating_add(skipped.len() as u64);
+        for sample in kept {
+            self.add_sample(*sample);
+        }
+    }
+
+    /// Returns a snapshot of the window's current statistics.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(10, 3);
+    /// ebrake.add_sample(false);
+    /// let snapshot = ebrake.snapshot();
+    /// assert_eq!(snapshot.failures, 1);
+    /// assert!(!snapshot.tripped);
+    /// ```
+    pub fn snapshot(&self) -> BrakeSnapshot {
+        BrakeSnapshot {
+            window_size: self.samples,
+            filled: self.data.len(),
+            failures: self.failures,
+            successes: self.successes,
+            failure_rate: self.failure_rate(),
+            tripped: self.should_trigger(),
+            total_samples: self.total_samples,
+            total_trips: self.total_trips,
+        }
+    }
+
+    /// Insert a sample whose failure counts `weight` times towards the tolerance,
+    /// so severe failures trip the emergency brake sooner than minor ones.
+    /// The weight is stored with the sample and subtracted again when it is evicted.
+    /// Weights do not affect a rate-based threshold, which counts failures.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(3, 4);
+    /// ebrake.add_sample(true);
+    /// ebrake.add_sample(true);
+    /// ebrake.add_weighted_sample(false, 5);
+    /// assert!(ebrake.should_trigger());
+    /// ```
+    pub fn add_weighted_sample(&mut self, success: bool, weight: usize) {
+        self.push_sample(success, weight, None);
+    }
+
+    /// Returns the weighted sum of failures in the window.
+    /// Without weighted samples, this is the number of failures.
+    pub fn failure_weight(&self) -> usize {
+        self.failure_weight
+    }
+
+    /// Changes the window size at runtime while keeping the recorded history.
+    /// Shrinking the window evicts the oldest samples until the window fits;
+    /// growing the window keeps every sample and waits for the new slots to fill
+    /// before the emergency brake can trigger again.
+    pub fn resize(&mut self, new_samples: usize) {
+        while self.data.len() > new_samples {
+            self.evict_oldest();
+        }
+        self.samples = new_samples;
+        self.refresh_warm_up();
+        #[cfg(feature = "metrics")]
+        self.record_window_metrics();
+    }
+
+    /// Changes the failure threshold at runtime. The tolerance must be less
+    /// than the window size, or the emergency brake could never be triggered.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(10, 3);
+    /// assert!(ebrake.set_tolerance(1).is_ok());
+    /// assert_eq!(ebrake.set_tolerance(10), Err(EBrakeError::ToleranceTooLarge));
+    /// ```
+    pub fn set_tolerance(&mut self, tolerance: usize) -> Result<(), EBrakeError> {
+        if tolerance >= self.samples {
+            return Err(EBrakeError::ToleranceTooLarge);
+        }
+
+        self.tolerance = tolerance;
+        Ok(())
+    }
+
+    /// Sets how many samples must be recorded before the emergency brake may
+    /// trigger. By default, the whole window must be filled. A smaller minimum
+    /// lets the brake trip during warm-up, once enough evidence is collected.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(10, 1);
+    /// ebrake.set_min_samples(3).unwrap();
+    /// ebrake.add_samples(&[false, false, true]);
+    /// assert!(ebrake.should_trigger());
+    /// ```
+    pub fn set_min_samples(&mut self, min_samples: usize) -> Result<(), EBrakeError> {
+        if min_samples > self.samples {
+            return Err(EBrakeError::MinSamplesTooLarge);
+        }
+
+        self.min_samples = Some(min_samples);
+        self.refresh_warm_up();
+        Ok(())
+    }
+
+    /// Returns true once the window holds enough samples for the windowed
+    /// thresholds to apply. See `EBrake` for which checks wait for it.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(10, 3);
+    /// ebrake.set_min_samples(2).unwrap();
+    /// ebrake.add_sample(true);
+    /// assert!(!ebrake.is_warmed_up());
+    /// ebrake.add_sample(true);
+    /// assert!(ebrake.is_warmed_up());
+    /// ```
+    pub fn is_warmed_up(&self) -> bool {
+        self.warmed_up
+    }
+
+    /// Returns how many samples must be recorded before the emergency brake may trigger.
+    /// This never exceeds the window size, even after the window is shrunk.
+    pub fn min_samples(&self) -> usize {
+        self.min_samples.map_or(self.samples, |min_samples| min_samples.min(self.samples))
+    }
+
+    /// Sets the name included in the brake's trip events, to tell several
+    /// brakes apart in the logs.
+    pub fn set_name(&mut self, name: &str) {
+        self.name = Some(name.to_string());
+    }
+
+    /// Returns the name of the brake, if one has been set.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Sets the level at which trip events are logged. Trips are logged at
+    /// `ERROR` by default; a lower level such as `WARN` suits a dry run with
+    /// `Trigger::Log`.
+    pub fn set_log_level(&mut self, level: Level) {
+        self.log_level = Some(level);
+    }
+
+    /// Returns the level at which trip events are logged.
+    pub fn log_level(&self) -> Level {
+        self.log_level.unwrap_or(Level::ERROR)
+    }
+
+    /// Returns the number of samples currently held in the window.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns true if the window holds no samples.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns true once the window holds as many samples as its configured size.
+    pub fn is_full(&self) -> bool {
+        self.data.len() == self.samples
+    }
+
+    /// Returns the oldest sample in the window, which is the next to be evicted,
+    /// or `None` if the window is empty.
+    pub fn peek_oldest(&self) -> Option<bool> {
+        self.data.front()
+    }
+
+    /// Returns the newest sample in the window, or `None` if the window is empty.
+    pub fn peek_newest(&self) -> Option<bool> {
+        self.data.back()
+    }
+
+    /// Iterates over the samples in the window from oldest to newest, without
+    /// copying the window.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(5, 2);
+    /// ebrake.add_samples(&[true, false, false, true]);
+    /// let longest_run = ebrake
+    ///     .samples_iter()
+    ///     .fold((0, 0), |(run, longest), sample| match sample {
+    ///         true => (0, longest),
+    ///         false => (run + 1, longest.max(run + 1)),
+    ///     })
+    ///     .1;
+    /// assert_eq!(longest_run, 2);
+    /// ```
+    pub fn samples_iter(&self) -> impl DoubleEndedIterator<Item = bool> + ExactSizeIterator + '_ {
+        self.data.iter()
+    }
+
+    /// Returns the samples in the window, oldest first.
+    pub fn to_vec(&self) -> Vec<bool> {
+        self.data.iter().collect()
+    }
+
+    /// Empties the window and returns its samples, oldest first, for example
+    /// to write the final window to a post-mortem log at shutdown. The window
+    /// counters are reset, while the lifetime totals and configuration are kept.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(3, 1);
+    /// ebrake.add_samples(&[true, false, true, false]);
+    /// assert_eq!(ebrake.drain(), [false, true, false]);
+    /// assert!(ebrake.is_empty());
+    /// ```
+    pub fn drain(&mut self) -> Vec<bool> {
+        let samples = self.to_vec();
+        self.clear_window();
+        samples
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for EBrake {
+    /// Summarizes the window, for example `EBrake[5/25 samples, 2 failures, 3 successes, tolerance 3, armed]`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "EBrake[{}/{} samples, {} failures, {} successes, tolerance {}, {}]",
+            self.len(),
+            self.samples,
+            self.failures,
+            self.successes,
+            self.tolerance,
+            match self.should_trigger() {
+                true => "tripped",
+                false => "armed",
+            },
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq for EBrake {
+    /// Compares the observable window: the samples in order, the failure and
+    /// success counts, the window size and the tolerance. Hooks, clocks,
+    /// clients and the remaining configuration are not compared.
+    fn eq(&self, other: &Self) -> bool {
+        self.data.iter().eq(other.data.iter())
+            && self.failures == other.failures
+            && self.successes == other.successes
+            && self.samples == other.samples
+            && self.tolerance == other.tolerance
+    }
+}
+
+#[cfg(feature = "std")]
+impl Eq for EBrake {}
+
+#[cfg(feature = "std")]
+impl Extend<bool> for EBrake {
+    /// Inserts each sample in turn, evicting the oldest samples as the window fills.
+    fn extend<I: IntoIterator<Item = bool>>(&mut self, samples: I) {
+        for sample in samples {
+            self.add_sample(sample);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl FromIterator<bool> for EBrake {
+    /// Collects the samples into an Emergency Brake whose window is exactly as
+    /// large as the number of samples collected, with a tolerance of zero.
+    /// To control the window size and tolerance, `extend` a configured brake instead.
+    /// ```
+    /// use emergency_brake::*;
+    /// let ebrake: EBrake = vec![true, true, false].into_iter().collect();
+    /// assert!(ebrake.is_full());
+    /// assert!(ebrake.should_trigger());
+    /// ```
+    fn from_iter<I: IntoIterator<Item = bool>>(samples: I) -> Self {
+        let samples: Vec<bool> = samples.into_iter().collect();
+        let mut ebrake = EBrake::new(samples.len(), 0);
+        ebrake.add_samples(&samples);
+        ebrake
     }
 }
 
 
 /// Test module for the Emergency Brake.
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod test;
 
+/// Helpers shared by the test modules.
+#[cfg(test)]
+mod test_util;
+
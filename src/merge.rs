@@ -0,0 +1,82 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+impl EBrake {
+    /// Merges the windows of two brakes into a new brake, for a global view of
+    /// sharded brakes. The merged brake:
+    ///
+    /// - has a window the size of both windows combined, so no sample is
+    ///   evicted, holding this brake's samples followed by `other`'s,
+    ///   each oldest first;
+    /// - sums the failure and success counts, failure weights, categories,
+    ///   tolerances, minimum samples and lifetime totals, so brakes of
+    ///   differing sizes contribute in proportion to their windows, and a
+    ///   failure rate threshold applies to the combined counts;
+    /// - keeps the longer of the two current failure streaks;
+    /// - takes every other setting, such as the failure rate, decay, trigger
+    ///   and name, from this brake, and starts closed and unlatched.
+    ///
+    /// Since the order of samples across shards is unknown, checks that depend
+    /// on it, such as decay and `should_trigger_recent`, treat `other`'s
+    /// samples as the newest.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut east = EBrake::new(4, 1);
+    /// east.add_samples(&[true, true, false, true]);
+    /// let mut west = EBrake::new(2, 0);
+    /// west.add_samples(&[false, false]);
+    ///
+    /// let global = east.merge(&west);
+    /// assert_eq!(global.snapshot().window_size, 6);
+    /// assert_eq!(global.current_failures(), 3);
+    /// assert!(global.should_trigger());
+    /// ```
+    pub fn merge(&self, other: &EBrake) -> EBrake {
+        let mut merged = self.clone();
+        merged.reset();
+        merged.samples = self.samples + other.samples;
+        merged.tolerance = self.tolerance + other.tolerance;
+        merged.min_samples = (self.min_samples.is_some() || other.min_samples.is_some()).then(|| self.min_samples() + other.min_samples());
+        merged.data = BitRing::with_capacity(merged.samples);
+        if self.weights.is_some() || other.weights.is_some() {
+            merged.weights = Some(VecDeque::with_capacity(merged.samples));
+        }
+        if self.categories.is_some() || other.categories.is_some() {
+            merged.categories = Some(CategoryWindow::default());
+        }
+
+        for brake in [self, other] {
+            let weights = brake.weights.iter().flatten().copied().chain(std::iter::repeat(1));
+            let categories = brake.categories.iter().flat_map(CategoryWindow::iter).chain(std::iter::repeat(None));
+            for ((sample, weight), category) in brake.data.iter().zip(weights).zip(categories) {
+                merged.data.push_back(sample);
+                if let Some(weights) = &mut merged.weights {
+                    weights.push_back(weight);
+                }
+                if let Some(merged_categories) = &mut merged.categories {
+                    merged_categories.push_back(category);
+                }
+            }
+        }
+        merged.failures = self.failures.saturating_add(other.failures);
+        merged.successes = self.successes.saturating_add(other.successes);
+        merged.failure_weight = self.failure_weight.saturating_add(other.failure_weight);
+        merged.failure_streak = self.failure_streak.max(other.failure_streak);
+        merged.total_samples = self.total_samples.saturating_add(other.total_samples);
+        merged.total_trips = self.total_trips.saturating_add(other.total_trips);
+        merged.refresh_warm_up();
+        // A merged brake that is already tripped does not count a new trip on its next sample.
+        merged.last_tripped = merged.should_trigger();
+        #[cfg(feature = "metrics")]
+        merged.record_window_metrics();
+        merged
+    }
+}
+
+/// Test module for merging brakes.
+#[cfg(test)]
+mod test;
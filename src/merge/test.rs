@@ -0,0 +1,85 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+#[test]
+/// Test that merging brakes of equal size combines their windows and tolerances.
+fn it_should_merge_brakes_of_equal_size() {
+    let mut a = EBrake::new(3, 1);
+    a.add_samples(&[true, false, true]);
+    let mut b = EBrake::new(3, 1);
+    b.add_samples(&[false, true, true]);
+
+    let merged = a.merge(&b);
+    assert_eq!(merged.samples, 6);
+    assert_eq!(merged.tolerance, 2);
+    assert_eq!(merged.to_vec(), [true, false, true, false, true, true]);
+    assert_eq!(merged.current_failures(), 2);
+    assert_eq!(merged.current_successes(), 4);
+    assert_eq!(merged.total_samples(), 6);
+    assert!(!merged.should_trigger());
+
+    // The shards themselves are unchanged.
+    assert_eq!(a.to_vec(), [true, false, true]);
+    assert_eq!(b.len(), 3);
+}
+
+#[test]
+/// Test that brakes of differing sizes contribute in proportion to their windows.
+fn it_should_merge_brakes_of_differing_sizes() {
+    let mut small = EBrake::new_with_rate(2, 0.5).unwrap();
+    small.add_samples(&[false, false]);
+    assert!(small.should_trigger());
+    let mut large = EBrake::new(8, 3);
+    large.add_samples(&[true; 8]);
+
+    let merged = small.merge(&large);
+    assert_eq!(merged.snapshot().window_size, 10);
+    assert_eq!(merged.snapshot().failure_rate, 0.2);
+    assert!(!merged.should_trigger());
+
+    // A partially filled shard makes the merged window partial too.
+    let mut partial = EBrake::new(4, 1);
+    partial.add_sample(false);
+    let merged = large.merge(&partial);
+    assert_eq!(merged.len(), 9);
+    assert!(!merged.is_warmed_up());
+}
+
+#[test]
+/// Test that the merged brake keeps weights, categories and the longer streak.
+fn it_should_merge_weights_categories_and_streaks() {
+    let mut a = EBrake::new(3, 4);
+    a.add_weighted_sample(false, 5);
+    a.add_samples(&[true, true]);
+    let mut b = EBrake::new(3, 0);
+    b.add_categorized_sample(Some(ErrorKind::Timeout));
+    b.add_categorized_sample(Some(ErrorKind::Timeout));
+    b.add_sample(true);
+
+    let merged = a.merge(&b);
+    assert_eq!(merged.failure_weight(), 7);
+    assert!(merged.should_trigger());
+    assert_eq!(merged.category_counts()[&ErrorKind::Timeout], 2);
+    assert_eq!(merged.current_streak(), 0);
+
+    let mut c = EBrake::new(3, 2);
+    c.add_samples(&[false, false, false]);
+    assert_eq!(b.merge(&c).current_streak(), 3);
+}
+
+#[test]
+/// Test that a merged brake that is already tripped counts its next trip only once.
+fn it_should_not_count_a_new_trip_after_merging() {
+    let mut a = EBrake::new(2, 0);
+    a.add_samples(&[false, false]);
+    let mut merged = a.merge(&a);
+    assert_eq!(merged.total_trips(), 2);
+
+    merged.add_sample(false);
+    assert!(merged.should_trigger());
+    assert_eq!(merged.total_trips(), 2);
+}
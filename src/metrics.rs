@@ -0,0 +1,73 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+/// The metric handles an EBrake reports through once metrics are registered.
+/// They are resolved once at registration, so recording a sample neither
+/// allocates nor looks the metrics up in the recorder.
+#[derive(Clone, Debug)]
+pub(crate) struct BrakeMetrics {
+    failures: ::metrics::Gauge,
+    successes: ::metrics::Gauge,
+    failure_rate: ::metrics::Gauge,
+    trips: ::metrics::Counter,
+}
+
+impl BrakeMetrics {
+    fn new(prefix: &str) -> Self {
+        let (failures, successes) = (format!("{}_failures", prefix), format!("{}_successes", prefix));
+        let (failure_rate, trips) = (format!("{}_failure_rate", prefix), format!("{}_trips_total", prefix));
+        ::metrics::describe_gauge!(failures.clone(), "Failures in the emergency brake window");
+        ::metrics::describe_gauge!(successes.clone(), "Successes in the emergency brake window");
+        ::metrics::describe_gauge!(failure_rate.clone(), "Fraction of failures in the emergency brake window");
+        ::metrics::describe_counter!(trips.clone(), "Times the emergency brake has tripped");
+        BrakeMetrics {
+            failures: ::metrics::gauge!(failures),
+            successes: ::metrics::gauge!(successes),
+            failure_rate: ::metrics::gauge!(failure_rate),
+            trips: ::metrics::counter!(trips),
+        }
+    }
+}
+
+impl EBrake {
+    /// Registers the emergency brake's metrics with the `metrics` facade under
+    /// the given prefix. Once registered, the failure count, success count and
+    /// failure rate gauges are updated on every sample, and the trip counter is
+    /// incremented every time the emergency brake trips, like `total_trips`.
+    /// The metrics are reported to the recorder installed when this is called.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(10, 3);
+    /// ebrake.register_metrics("checkout_api");
+    /// ebrake.add_sample(true); // Updates `checkout_api_successes`.
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+    pub fn register_metrics(&mut self, prefix: &str) {
+        self.metrics = Some(BrakeMetrics::new(prefix));
+        self.record_window_metrics();
+    }
+
+    /// Updates the window gauges, if metrics are registered.
+    pub(crate) fn record_window_metrics(&self) {
+        if let Some(metrics) = &self.metrics {
+            metrics.failures.set(self.failures as f64);
+            metrics.successes.set(self.successes as f64);
+            metrics.failure_rate.set(self.failure_rate());
+        }
+    }
+
+    /// Increments the trip counter, if metrics are registered.
+    pub(crate) fn record_trip_metrics(&self) {
+        if let Some(metrics) = &self.metrics {
+            metrics.trips.increment(1);
+        }
+    }
+}
+
+/// Test module for the metrics integration.
+#[cfg(test)]
+mod test;
@@ -0,0 +1,82 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+use metrics_util::{CompositeKey, MetricKind};
+use std::collections::HashMap;
+
+/// Runs the closure with a local debugging recorder and returns the recorded values by name.
+fn record(f: impl FnOnce()) -> HashMap<(MetricKind, String), DebugValue> {
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    ::metrics::with_local_recorder(&recorder, f);
+    snapshotter
+        .snapshot()
+        .into_vec()
+        .into_iter()
+        .map(|(key, _, _, value): (CompositeKey, _, _, _)| ((key.kind(), key.key().name().to_string()), value))
+        .collect()
+}
+
+fn gauge(values: &HashMap<(MetricKind, String), DebugValue>, name: &str) -> f64 {
+    match values.get(&(MetricKind::Gauge, name.to_string())) {
+        Some(DebugValue::Gauge(value)) => value.into_inner(),
+        other => panic!("expected gauge {}, found {:?}", name, other),
+    }
+}
+
+#[test]
+/// Test that the window gauges and trip counter are updated.
+fn it_should_update_registered_metrics() {
+    let values = record(|| {
+        let mut ebrake = EBrake::new(4, 1);
+        ebrake.register_metrics("test_brake");
+        ebrake.extend([true, false, false, false]);
+        ebrake.trigger(&Trigger::Log);
+    });
+
+    assert_eq!(gauge(&values, "test_brake_failures"), 3.0);
+    assert_eq!(gauge(&values, "test_brake_successes"), 1.0);
+    assert_eq!(gauge(&values, "test_brake_failure_rate"), 0.75);
+    assert_eq!(
+        values.get(&(MetricKind::Counter, "test_brake_trips_total".to_string())),
+        Some(&DebugValue::Counter(1))
+    );
+}
+
+#[test]
+/// Test that the trip counter counts trips, like total_trips, rather than trigger firings.
+fn it_should_count_each_trip_once() {
+    let mut total_trips = 0;
+    let values = record(|| {
+        let mut ebrake = EBrake::new(2, 0);
+        ebrake.register_metrics("trip_brake");
+        ebrake.extend([false, false]);
+        ebrake.trigger(&Trigger::Log);
+        ebrake.trigger(&Trigger::Log);
+
+        ebrake.extend([true, true, false]);
+        ebrake.trigger(&Trigger::Log);
+        total_trips = ebrake.total_trips();
+    });
+
+    assert_eq!(total_trips, 2);
+    assert_eq!(
+        values.get(&(MetricKind::Counter, "trip_brake_trips_total".to_string())),
+        Some(&DebugValue::Counter(2))
+    );
+}
+
+#[test]
+/// Test that nothing is reported until metrics are registered.
+fn it_should_not_report_unregistered_metrics() {
+    let values = record(|| {
+        let mut ebrake = EBrake::new(2, 0);
+        ebrake.extend([false, false]);
+        ebrake.trigger(&Trigger::Log);
+    });
+    assert!(values.is_empty());
+}
@@ -0,0 +1,80 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+impl EBrake {
+    /// Saves the brake's configuration, sample window and counters to a file
+    /// in a compact binary format, so it can be restored after a restart.
+    /// The latched and disarmed states and the log level are saved with the
+    /// configuration, so a latched fail-safe brake stays tripped across a
+    /// restart. As with the `serde` feature, runtime state such as hooks,
+    /// clocks, metrics and the circuit breaker state is not saved.
+    ///
+    /// The brake is written to a temporary file next to `path`, which is
+    /// synced and then renamed over `path`, so a crash or failed save leaves
+    /// the previous file intact.
+    ///
+    /// Returns an error if the file cannot be written, or if the default
+    /// trigger is a `Callback`, which cannot be persisted.
+    #[cfg_attr(docsrs, doc(cfg(feature = "persist")))]
+    pub fn save_to_path(&self, path: &Path) -> io::Result<()> {
+        let temp = temp_path_for(path)?;
+        let saved = self.write_to_file(&temp).and_then(|()| std::fs::rename(&temp, path));
+        if saved.is_err() {
+            let _ = std::fs::remove_file(&temp);
+        }
+        saved
+    }
+
+    /// Writes the brake to a new file and syncs it to disk.
+    fn write_to_file(&self, path: &Path) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        bincode::serialize_into(&mut writer, self).map_err(|error| into_io_error(*error))?;
+        writer.flush()?;
+        writer.get_ref().sync_all()
+    }
+
+    /// Loads a brake saved with `save_to_path`. Returns an
+    /// `io::ErrorKind::InvalidData` error if the file cannot be decoded, or
+    /// if its counters or buffers disagree with its sample window.
+    /// ```no_run
+    /// use emergency_brake::*;
+    /// use std::path::{Path, PathBuf};
+    /// let path = Path::new("/var/lib/checkout/ebrake.bin");
+    /// let ebrake = EBrake::load_from_path(path).unwrap_or_else(|_| EBrake::new(25, 3));
+    /// ebrake.save_to_path(path).unwrap();
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "persist")))]
+    pub fn load_from_path(path: &Path) -> io::Result<EBrake> {
+        let reader = BufReader::new(File::open(path)?);
+        bincode::deserialize_from(reader).map_err(|error| into_io_error(*error))
+    }
+}
+
+/// Returns the temporary file a save to `path` is written to before being
+/// renamed into place. It is a sibling of `path`, so the rename stays on one
+/// file system.
+fn temp_path_for(path: &Path) -> io::Result<PathBuf> {
+    let mut name = path.file_name().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "the path has no file name"))?.to_os_string();
+    name.push(".tmp");
+    Ok(path.with_file_name(name))
+}
+
+/// Converts an encoding error into an I/O error, keeping I/O errors as they are.
+fn into_io_error(error: bincode::ErrorKind) -> io::Error {
+    match error {
+        bincode::ErrorKind::Io(error) => error,
+        error => io::Error::new(io::ErrorKind::InvalidData, error),
+    }
+}
+
+/// Test module for persisting the emergency brake.
+#[cfg(test)]
+mod test;
@@ -0,0 +1,131 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+use std::path::PathBuf;
+
+/// Returns a path in the temporary directory that is unique to the test.
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("emergency_brake_{}_{}.bin", std::process::id(), name))
+}
+
+#[test]
+/// Test that a restored brake has the same window and behaves identically.
+fn it_should_round_trip_through_a_file() {
+    let path = temp_path("round_trip");
+    let mut original = EBrake::new(6, 2);
+    original.set_min_samples(4).unwrap();
+    original.add_weighted_sample(false, 2);
+    original.add_categorized_sample(Some(ErrorKind::Timeout));
+    original.extend([true, true, true, true, true]);
+    original.save_to_path(&path).unwrap();
+
+    let mut restored = EBrake::load_from_path(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(restored.snapshot(), original.snapshot());
+    assert_eq!(restored.failure_weight(), original.failure_weight());
+    assert_eq!(restored.category_counts(), original.category_counts());
+    assert!(restored.samples_iter().eq(original.samples_iter()));
+
+    for sample in [false, false, true, false, false, false, true] {
+        original.add_sample(sample);
+        restored.add_sample(sample);
+        assert_eq!(restored.snapshot(), original.snapshot());
+    }
+}
+
+#[test]
+/// Test that loading a missing or corrupt file reports an error.
+fn it_should_reject_a_missing_or_corrupt_file() {
+    let path = temp_path("corrupt");
+    assert_eq!(EBrake::load_from_path(&path).unwrap_err().kind(), io::ErrorKind::NotFound);
+
+    std::fs::write(&path, b"not a brake").unwrap();
+    let error = EBrake::load_from_path(&path).unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(error.kind(), io::ErrorKind::InvalidData | io::ErrorKind::UnexpectedEof));
+}
+
+#[test]
+/// Test that a callback default trigger cannot be saved.
+fn it_should_refuse_to_save_a_callback_trigger() {
+    fn callback() {}
+    let path = temp_path("callback");
+    let ebrake = EBrake::builder().window_size(2).default_trigger(Trigger::Callback(callback)).build().unwrap();
+    assert!(ebrake.save_to_path(&path).is_err());
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+/// Test that a failed save leaves the previously saved file loadable.
+fn it_should_keep_the_previous_file_when_a_save_fails() {
+    fn callback() {}
+    let path = temp_path("failed_save");
+    let mut saved = EBrake::new(4, 1);
+    saved.extend([false, true, true]);
+    saved.save_to_path(&path).unwrap();
+
+    let unsaveable = EBrake::builder().window_size(2).default_trigger(Trigger::Callback(callback)).build().unwrap();
+    assert!(unsaveable.save_to_path(&path).is_err());
+    assert!(!temp_path_for(&path).unwrap().exists());
+
+    let restored = EBrake::load_from_path(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(restored.snapshot(), saved.snapshot());
+}
+
+#[test]
+/// Test that a file whose counters disagree with its window is rejected.
+fn it_should_reject_an_inconsistent_window() {
+    let path = temp_path("inconsistent");
+    let mut valid = EBrake::new(4, 1);
+    valid.extend([true, false, true]);
+    valid.add_weighted_sample(false, 3);
+
+    let mut too_many_failures = valid.clone();
+    too_many_failures.failures = 9;
+    let mut unbounded = valid.clone();
+    unbounded.samples = 1;
+    let mut miscounted = valid.clone();
+    miscounted.failures = 3;
+    miscounted.successes = 1;
+    let mut short_weights = valid.clone();
+    short_weights.weights.as_mut().unwrap().pop_front();
+    let mut categorized = valid.clone();
+    categorized.add_categorized_sample(Some(ErrorKind::Timeout));
+    categorized.categories.as_mut().unwrap().pop_front();
+    for corrupt in [too_many_failures, unbounded, miscounted, short_weights, categorized] {
+        corrupt.save_to_path(&path).unwrap();
+        assert_eq!(EBrake::load_from_path(&path).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    valid.save_to_path(&path).unwrap();
+    let restored = EBrake::load_from_path(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(restored, valid);
+}
+
+#[test]
+/// Test that a latched or disarmed brake and its log level survive a restart.
+fn it_should_restore_the_latched_and_disarmed_states() {
+    let path = temp_path("latched");
+    let mut latched = EBrake::builder().window_size(2).failure_threshold(0).latching(true).log_level(tracing::Level::WARN).build().unwrap();
+    latched.extend([false, false, true, true]);
+    assert!(latched.is_latched());
+    latched.save_to_path(&path).unwrap();
+
+    let mut restored = EBrake::load_from_path(&path).unwrap();
+    assert!(restored.is_latched());
+    assert_eq!(restored.log_level(), tracing::Level::WARN);
+    restored.add_sample(true);
+    assert!(restored.should_trigger());
+
+    let mut disarmed = EBrake::new(2, 0);
+    disarmed.disarm();
+    disarmed.save_to_path(&path).unwrap();
+    let restored = EBrake::load_from_path(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(!restored.is_armed());
+}
@@ -0,0 +1,103 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+use hooks::Hook;
+use std::sync::Arc;
+
+/// The TripPredicate trait decides whether an emergency brake trips, for
+/// conditions the built-in thresholds do not cover. The snapshot describes
+/// the window being judged, and its `tripped` field holds the verdict of the
+/// built-in thresholds, so a predicate can either refine or replace it.
+/// Closures taking a `&BrakeSnapshot` implement the trait.
+/// ```
+/// use emergency_brake::*;
+///
+/// /// Trips once half the window has failed, but only after ten samples.
+/// struct HalfFailed;
+///
+/// impl TripPredicate for HalfFailed {
+///     fn should_trip(&self, snapshot: &BrakeSnapshot) -> bool {
+///         snapshot.filled >= 10 && snapshot.failure_rate > 0.5
+///     }
+/// }
+///
+/// let mut ebrake = EBrake::new(20, 0);
+/// ebrake.set_trip_predicate(HalfFailed);
+/// ebrake.add_samples(&[false; 9]);
+/// assert!(!ebrake.should_trigger());
+/// ebrake.add_sample(false);
+/// assert!(ebrake.should_trigger());
+/// ```
+pub trait TripPredicate {
+    /// Returns true if a brake with the given window should trip.
+    fn should_trip(&self, snapshot: &BrakeSnapshot) -> bool;
+}
+
+impl<F: Fn(&BrakeSnapshot) -> bool> TripPredicate for F {
+    fn should_trip(&self, snapshot: &BrakeSnapshot) -> bool {
+        self(snapshot)
+    }
+}
+
+/// A trip predicate registered on an emergency brake.
+pub(crate) type PredicateHook = Hook<dyn TripPredicate + Send + Sync>;
+
+impl EBrake {
+    /// Sets a predicate that decides whether the window trips the brake,
+    /// in place of the built-in thresholds. The predicate is consulted by
+    /// `should_trigger`, `would_trigger` and the trigger methods, and the
+    /// cooldown, latching and minimum trip interval still apply to its verdict.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(10, 2);
+    /// // Trip on the failure threshold only while no success has been seen.
+    /// ebrake.set_trip_predicate(|snapshot: &BrakeSnapshot| snapshot.tripped && snapshot.successes == 0);
+    /// ebrake.add_samples(&[true, false, false, false]);
+    /// assert!(!ebrake.should_trigger());
+    /// ```
+    pub fn set_trip_predicate(&mut self, predicate: impl TripPredicate + Send + Sync + 'static) {
+        self.trip_predicate = Some(Hook(Arc::new(predicate)));
+    }
+
+    /// Removes the trip predicate, returning to the built-in thresholds.
+    pub fn clear_trip_predicate(&mut self) {
+        self.trip_predicate = None;
+    }
+
+    /// Returns true if a trip predicate has been set.
+    pub fn has_trip_predicate(&self) -> bool {
+        self.trip_predicate.is_some()
+    }
+
+    /// Returns the predicate's verdict on a window holding `len` samples with
+    /// the given failures, where `tripped` is the verdict of the built-in
+    /// thresholds. The lifetime totals are those of the brake, even when the
+    /// window is hypothetical.
+    pub(crate) fn apply_trip_predicate(&self, len: usize, failures: usize, tripped: bool) -> bool {
+        let Some(predicate) = &self.trip_predicate else {
+            return tripped;
+        };
+        let failure_rate = match len {
+            0 => 0.0,
+            len => failures as f64 / len as f64,
+        };
+        predicate.should_trip(&BrakeSnapshot {
+            window_size: self.samples,
+            filled: len,
+            failures,
+            successes: len - failures,
+            failure_rate,
+            tripped,
+            total_samples: self.total_samples,
+            total_trips: self.total_trips,
+        })
+    }
+}
+
+/// Test module for the trip predicates.
+#[cfg(test)]
+mod test;
@@ -0,0 +1,75 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+/// Trips when the failure rate exceeds the limit with at least `min_samples` in the window.
+struct RateWithMinimum {
+    min_samples: usize,
+    rate: f64,
+}
+
+impl TripPredicate for RateWithMinimum {
+    fn should_trip(&self, snapshot: &BrakeSnapshot) -> bool {
+        snapshot.failure_rate > self.rate && snapshot.filled >= self.min_samples
+    }
+}
+
+#[test]
+/// Test that a predicate combining a failure rate and a sample count decides the trip.
+fn it_should_trip_on_a_custom_predicate() {
+    let mut ebrake = EBrake::new(20, 0);
+    assert!(!ebrake.has_trip_predicate());
+    ebrake.set_trip_predicate(RateWithMinimum { min_samples: 10, rate: 0.5 });
+    assert!(ebrake.has_trip_predicate());
+
+    // Every sample fails, but there are too few of them.
+    ebrake.add_samples(&[false; 9]);
+    assert!(!ebrake.should_trigger());
+    assert!(ebrake.would_trigger(false));
+    assert!(ebrake.would_trigger(true));
+
+    // Six failures in ten samples.
+    let mut ebrake = EBrake::new(20, 0);
+    ebrake.set_trip_predicate(RateWithMinimum { min_samples: 10, rate: 0.5 });
+    ebrake.add_samples(&[true, true, true, true, false, false, false, false, false]);
+    assert!(!ebrake.should_trigger());
+    assert!(ebrake.trigger_on_sample(false, &Trigger::Log).is_tripped());
+    assert!(ebrake.snapshot().tripped);
+
+    // Six failures in twelve samples.
+    ebrake.add_sample(true);
+    assert!(ebrake.should_trigger());
+    ebrake.add_sample(true);
+    assert!(!ebrake.should_trigger());
+}
+
+#[test]
+/// Test that the predicate sees the verdict of the built-in thresholds and can be cleared.
+fn it_should_refine_the_built_in_thresholds() {
+    let mut ebrake = EBrake::new(4, 1);
+    ebrake.set_trip_predicate(|snapshot: &BrakeSnapshot| snapshot.tripped && snapshot.successes == 0);
+    ebrake.add_samples(&[true, false, false, false]);
+    assert!(!ebrake.should_trigger());
+
+    ebrake.add_sample(false);
+    assert!(ebrake.should_trigger());
+
+    ebrake.add_sample(true);
+    assert!(!ebrake.should_trigger());
+    ebrake.clear_trip_predicate();
+    assert!(ebrake.should_trigger());
+}
+
+#[test]
+/// Test that the cooldown applies to a trip decided by the predicate.
+fn it_should_apply_the_cooldown_to_the_predicate() {
+    let mut ebrake = EBrake::new(3, 2);
+    ebrake.set_cooldown(Duration::from_secs(60));
+    ebrake.set_trip_predicate(|snapshot: &BrakeSnapshot| snapshot.failures >= 1);
+    ebrake.add_sample(false);
+    assert!(ebrake.should_trigger());
+    assert!(matches!(ebrake.state(), BrakeState::Open { .. }));
+}
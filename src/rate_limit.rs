@@ -0,0 +1,86 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::Instant;
+
+/// Limits how often the trigger action runs while the brake stays tripped.
+/// The triggers take `&self`, so the last firing is kept behind a lock.
+#[derive(Debug)]
+pub(crate) struct RateLimit {
+    interval: Duration,
+    last_fired: Mutex<Option<Instant>>,
+}
+
+impl RateLimit {
+    fn new(interval: Duration) -> Self {
+        RateLimit {
+            interval,
+            last_fired: Mutex::new(None),
+        }
+    }
+
+    /// Returns true, and records the firing, if the interval has elapsed since the last one.
+    pub(crate) fn allow(&self, now: Instant) -> bool {
+        let mut last_fired = self.last_fired();
+        match *last_fired {
+            Some(last) if now.saturating_duration_since(last) < self.interval => false,
+            _ => {
+                *last_fired = Some(now);
+                true
+            },
+        }
+    }
+
+    /// Forgets the last firing, so the next trip fires immediately.
+    pub(crate) fn clear(&self) {
+        *self.last_fired() = None;
+    }
+
+    fn last_fired(&self) -> MutexGuard<'_, Option<Instant>> {
+        self.last_fired.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Clone for RateLimit {
+    fn clone(&self) -> Self {
+        RateLimit {
+            interval: self.interval,
+            last_fired: Mutex::new(*self.last_fired()),
+        }
+    }
+}
+
+impl EBrake {
+    /// Rate limits the trigger action, so that while the brake stays tripped
+    /// the action, its trip event and the trigger hook run at most once per
+    /// interval. Within the interval, the trigger methods return as if the
+    /// brake had not tripped. This collapses a storm of trips in `Log` or
+    /// `Callback` mode into periodic notifications. Each new trip fires
+    /// immediately. The interval is measured with the brake's clock.
+    /// ```
+    /// use emergency_brake::*;
+    /// use std::time::Duration;
+    /// let mut ebrake = EBrake::new(2, 0);
+    /// ebrake.set_trigger_rate_limit(Duration::from_secs(60));
+    /// ebrake.add_samples(&[false, false]);
+    /// assert_eq!(ebrake.trigger(&Trigger::Log), TripOutcome::Handled);
+    /// assert_eq!(ebrake.trigger_on_sample(false, &Trigger::Log), TripOutcome::NotTripped);
+    /// ```
+    pub fn set_trigger_rate_limit(&mut self, interval: Duration) {
+        self.trigger_limit = Some(RateLimit::new(interval));
+    }
+
+    /// Removes the trigger rate limit.
+    pub fn clear_trigger_rate_limit(&mut self) {
+        self.trigger_limit = None;
+    }
+}
+
+/// Test module for the trigger rate limit.
+#[cfg(test)]
+mod test;
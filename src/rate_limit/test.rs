@@ -0,0 +1,69 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+use crate::test::capture_events;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+fn trip_events(ebrake: &mut EBrake, samples: usize) -> usize {
+    let events = capture_events(|| {
+        for _ in 0..samples {
+            ebrake.trigger_on_sample(false, &Trigger::Log);
+        }
+    });
+    events.iter().filter(|event| event.fields["message"] == "Emergency brake triggered!").count()
+}
+
+#[test]
+/// Test that only one trip is logged within the interval across many triggering samples.
+fn it_should_log_once_per_interval() {
+    let clock = MockClock::new();
+    let mut ebrake = EBrake::new(2, 0);
+    ebrake.set_clock(clock.clone());
+    ebrake.set_trigger_rate_limit(Duration::from_secs(60));
+    assert_eq!(trip_events(&mut ebrake, 50), 1);
+
+    clock.advance(Duration::from_secs(59));
+    assert_eq!(trip_events(&mut ebrake, 10), 0);
+    assert!(ebrake.should_trigger());
+
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(trip_events(&mut ebrake, 10), 1);
+}
+
+#[test]
+/// Test that a new trip fires immediately after the brake recovers.
+fn it_should_fire_a_new_trip_immediately() {
+    let clock = MockClock::new();
+    let mut ebrake = EBrake::new(2, 0);
+    ebrake.set_clock(clock);
+    ebrake.set_trigger_rate_limit(Duration::from_secs(60));
+    assert_eq!(trip_events(&mut ebrake, 3), 1);
+
+    ebrake.extend([true, true]);
+    assert!(!ebrake.should_trigger());
+    assert_eq!(trip_events(&mut ebrake, 3), 1);
+}
+
+#[test]
+/// Test that the trigger hook is rate limited along with the action.
+fn it_should_rate_limit_the_trigger_hook() {
+    let fired = Arc::new(AtomicUsize::new(0));
+    let recorded = Arc::clone(&fired);
+    let mut ebrake = EBrake::new(1, 0);
+    ebrake.set_on_trigger(move |_| {
+        recorded.fetch_add(1, Ordering::SeqCst);
+    });
+    ebrake.set_trigger_rate_limit(Duration::from_secs(3600));
+    for _ in 0..5 {
+        ebrake.trigger_on_sample(false, &Trigger::Log);
+    }
+    assert_eq!(fired.load(Ordering::SeqCst), 1);
+
+    ebrake.clear_trigger_rate_limit();
+    assert!(ebrake.trigger(&Trigger::Log).is_tripped());
+    assert_eq!(fired.load(Ordering::SeqCst), 2);
+}
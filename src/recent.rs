@@ -0,0 +1,33 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+impl EBrake {
+    /// Returns the number of failures among the newest `k` samples in the window.
+    /// If fewer than `k` samples have been recorded, all of them are examined.
+    pub fn recent_failures(&self, k: usize) -> usize {
+        self.data.iter().rev().take(k).filter(|sample| !sample).count()
+    }
+
+    /// Returns true if at least `threshold` of the newest `k` samples are
+    /// failures, such as 3 of the last 5. Since older samples are ignored, this
+    /// detects a sudden cluster of failures sooner than the full window count.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(20, 10);
+    /// ebrake.add_samples(&[true; 15]);
+    /// ebrake.add_samples(&[false, true, false, false, true]);
+    /// assert!(ebrake.should_trigger_recent(5, 3));
+    /// assert!(!ebrake.should_trigger());
+    /// ```
+    pub fn should_trigger_recent(&self, k: usize, threshold: usize) -> bool {
+        self.recent_failures(k) >= threshold
+    }
+}
+
+/// Test module for the recent sample rule.
+#[cfg(test)]
+mod test;
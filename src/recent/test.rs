@@ -0,0 +1,38 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+#[test]
+/// Test that old successes do not mask a recent cluster of failures.
+fn it_should_not_mask_recent_failures() {
+    let mut ebrake = EBrake::new(50, 10);
+    ebrake.extend([true; 45]);
+    ebrake.extend([false, false, true, false, true]);
+    assert_eq!(ebrake.recent_failures(5), 3);
+    assert!(ebrake.should_trigger_recent(5, 3));
+    assert!(!ebrake.should_trigger_recent(5, 4));
+    assert!(!ebrake.should_trigger());
+}
+
+#[test]
+/// Test that failures older than the newest samples are ignored.
+fn it_should_ignore_older_failures() {
+    let mut ebrake = EBrake::new(10, 5);
+    ebrake.extend([false, false, false, true, true, true, true, true]);
+    assert_eq!(ebrake.recent_failures(5), 0);
+    assert!(!ebrake.should_trigger_recent(5, 1));
+    assert_eq!(ebrake.recent_failures(8), 3);
+}
+
+#[test]
+/// Test that a partially filled window examines every sample.
+fn it_should_examine_a_partial_window() {
+    let mut ebrake = EBrake::new(10, 5);
+    ebrake.extend([false, false]);
+    assert_eq!(ebrake.recent_failures(5), 2);
+    assert!(ebrake.should_trigger_recent(5, 2));
+    assert!(!ebrake.should_trigger_recent(5, 3));
+}
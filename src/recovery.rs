@@ -0,0 +1,209 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+use std::time::{Duration, Instant};
+
+/// The BrakeState enum is the circuit breaker state of an emergency brake with a cooldown.
+///
+/// * `Closed` is the normal state. When the window crosses its threshold, the
+///   brake trips and moves to `Open`.
+/// * `Open` suppresses further trips until the cooldown has elapsed. Samples are
+///   still recorded.
+/// * `HalfOpen` is entered once the cooldown has elapsed. The next samples are
+///   probes: once `half_open_max_probes` of them succeed, which defaults to one,
+///   the brake moves to `Closed` with an empty window, and any failure trips
+///   the brake again and restarts the cooldown.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BrakeState {
+    /// The brake is armed and recording samples.
+    #[default]
+    Closed,
+
+    /// The brake has tripped and will not trip again until the given instant.
+    Open {
+        /// The instant at which the cooldown ends.
+        until: Instant,
+    },
+
+    /// The cooldown has elapsed and the next sample decides whether to close or trip again.
+    HalfOpen,
+}
+
+/// The RecoveryEstimate enum describes what it would take for a tripped
+/// emergency brake to recover, as returned by `EBrake::recovery_estimate`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecoveryEstimate {
+    /// The brake is not tripped.
+    Healthy,
+
+    /// The cooldown ends after the given duration, at which point the brake
+    /// becomes half-open.
+    Cooldown(Duration),
+
+    /// The half-open brake closes after the given number of successful probes.
+    Probes(usize),
+
+    /// The window stops tripping after the given number of consecutive successes.
+    Samples(usize),
+
+    /// The brake is latched and only recovers through `reset`.
+    Reset,
+}
+
+impl EBrake {
+    /// Estimates what it would take for the emergency brake to recover: the
+    /// rest of the cooldown or the remaining probes with a cooldown, otherwise
+    /// how many consecutive successes would stop the window from tripping.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(4, 1);
+    /// ebrake.add_samples(&[false, false, false, true]);
+    /// assert_eq!(ebrake.recovery_estimate(), RecoveryEstimate::Samples(2));
+    /// ```
+    pub fn recovery_estimate(&self) -> RecoveryEstimate {
+        if self.latched {
+            return RecoveryEstimate::Reset;
+        }
+
+        match (self.cooldown, self.state()) {
+            (Some(_), BrakeState::Open { until }) => RecoveryEstimate::Cooldown(until.saturating_duration_since(self.clock.now())),
+            (Some(_), BrakeState::HalfOpen) => RecoveryEstimate::Probes(self.remaining_probes()),
+            (Some(_), BrakeState::Closed) => RecoveryEstimate::Healthy,
+            (None, _) if !self.window_tripped() => RecoveryEstimate::Healthy,
+            (None, _) => RecoveryEstimate::Samples(self.successes_to_recover()),
+        }
+    }
+
+    /// Returns how many consecutive successes would stop the window from tripping.
+    fn successes_to_recover(&self) -> usize {
+        let weights = self.weights.iter().flatten().copied().chain(std::iter::repeat(1));
+        let mut oldest = self.data.iter().zip(weights);
+        let (mut len, mut failures, mut failure_weight) = (self.data.len(), self.failures, self.failure_weight);
+        for successes in 1..self.samples {
+            match len == self.samples {
+                true => {
+                    if let Some((false, weight)) = oldest.next() {
+                        failures = failures.saturating_sub(1);
+                        failure_weight = failure_weight.saturating_sub(weight);
+                    }
+                },
+                false => len += 1,
+            }
+            let decayed = self.decay.map(|decay| {
+                let added = successes.min(len);
+                let kept = self.weighted_newest_first().take(len - added);
+                decayed_score(std::iter::repeat_n((true, 1), added).chain(kept), decay)
+            });
+            if !self.tripped_with(len, failures, failure_weight, 0, decayed) {
+                return successes;
+            }
+        }
+
+        // A window of successes never trips.
+        self.samples
+    }
+
+    /// Enables circuit breaker recovery with the given cooldown.
+    /// With a cooldown, `should_trigger` only returns true for the sample that
+    /// tripped the brake, so a non-terminating trigger fires once per trip
+    /// rather than on every sample. See `BrakeState` for the transitions.
+    pub fn set_cooldown(&mut self, cooldown: Duration) {
+        self.cooldown = Some(cooldown);
+    }
+
+    /// Sets the clock used for time-based behavior such as the cooldown.
+    pub fn set_clock(&mut self, clock: impl Clock + 'static) {
+        self.clock = SharedClock::new(clock);
+    }
+
+    /// Returns the current circuit breaker state.
+    /// An `Open` brake whose cooldown has elapsed is reported as `HalfOpen`.
+    pub fn state(&self) -> BrakeState {
+        match self.state {
+            BrakeState::Open { until } if self.clock.now() >= until => BrakeState::HalfOpen,
+            state => state,
+        }
+    }
+
+    /// Advances the circuit breaker state after a sample has been recorded.
+    pub(crate) fn update_state(&mut self, sample: bool) {
+        self.tripped = false;
+        self.update_latch();
+        let Some(cooldown) = self.cooldown else {
+            return;
+        };
+
+        match self.state() {
+            BrakeState::Closed if self.window_tripped() => self.open(cooldown),
+            BrakeState::HalfOpen if sample => {
+                self.probes += 1;
+                self.state = BrakeState::HalfOpen;
+                if self.probes >= self.half_open_max_probes() {
+                    self.state = BrakeState::Closed;
+                    self.probes = 0;
+                    self.clear_window();
+                }
+            },
+            BrakeState::HalfOpen => self.open(cooldown),
+            _ => {},
+        }
+    }
+
+    fn open(&mut self, cooldown: Duration) {
+        self.state = BrakeState::Open {
+            until: self.clock.now() + cooldown,
+        };
+        self.probes = 0;
+        self.tripped = true;
+    }
+
+    /// Sets how many probe samples a half-open brake admits before deciding.
+    /// The brake closes once that many probes have succeeded, and opens again
+    /// with a fresh cooldown as soon as one fails. Defaults to a single probe;
+    /// a value of zero is treated as one.
+    /// ```
+    /// use emergency_brake::*;
+    /// use std::time::Duration;
+    /// let clock = MockClock::new();
+    /// let mut ebrake = EBrake::builder()
+    ///     .window_size(2)
+    ///     .cooldown(Duration::from_secs(30))
+    ///     .half_open_max_probes(3)
+    ///     .clock(clock.clone())
+    ///     .build()
+    ///     .unwrap();
+    /// ebrake.add_samples(&[false, false]);
+    /// clock.advance(Duration::from_secs(30));
+    /// ebrake.add_samples(&[true, true]);
+    /// assert_eq!(ebrake.remaining_probes(), 1);
+    /// ebrake.add_sample(true);
+    /// assert_eq!(ebrake.state(), BrakeState::Closed);
+    /// ```
+    pub fn set_half_open_max_probes(&mut self, max_probes: usize) {
+        self.half_open_max_probes = Some(max_probes.max(1));
+    }
+
+    /// Returns how many probe samples a half-open brake admits before deciding.
+    pub fn half_open_max_probes(&self) -> usize {
+        self.half_open_max_probes.unwrap_or(1)
+    }
+
+    /// Returns how many more successful probes a half-open brake needs to
+    /// close, or zero if the brake is not half-open. Callers can use this to
+    /// limit the traffic sent to a dependency that is still recovering.
+    pub fn remaining_probes(&self) -> usize {
+        match self.state() {
+            BrakeState::HalfOpen => self.half_open_max_probes() - self.probes,
+            _ => 0,
+        }
+    }
+}
+
+
+/// Test module for circuit breaker recovery.
+#[cfg(test)]
+mod test;
@@ -0,0 +1,183 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+fn cooldown_brake(clock: &MockClock) -> EBrake {
+    let mut ebrake = EBrake::new(4, 1);
+    ebrake.set_cooldown(Duration::from_secs(30));
+    ebrake.set_clock(clock.clone());
+    ebrake
+}
+
+#[test]
+/// Test the full trip, open, half-open and closed recovery cycle.
+fn it_should_recover_through_half_open() {
+    let clock = MockClock::new();
+    let mut ebrake = cooldown_brake(&clock);
+    ebrake.extend([true, true, false]);
+    assert_eq!(ebrake.state(), BrakeState::Closed);
+
+    assert!(ebrake.trigger_on_sample(false, &Trigger::Log).is_tripped());
+    assert!(matches!(ebrake.state(), BrakeState::Open { .. }));
+
+    // Open: the brake does not fire again while cooling down.
+    assert!(!ebrake.trigger_on_sample(false, &Trigger::Log).is_tripped());
+    clock.advance(Duration::from_secs(29));
+    assert!(!ebrake.trigger_on_sample(false, &Trigger::Log).is_tripped());
+
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(ebrake.state(), BrakeState::HalfOpen);
+
+    // A successful probe closes the brake with a fresh window.
+    assert!(!ebrake.trigger_on_sample(true, &Trigger::Log).is_tripped());
+    assert_eq!(ebrake.state(), BrakeState::Closed);
+    assert!(ebrake.is_empty());
+}
+
+#[test]
+/// Test that a failed probe trips the brake again and restarts the cooldown.
+fn it_should_reopen_on_a_failed_probe() {
+    let clock = MockClock::new();
+    let mut ebrake = cooldown_brake(&clock);
+    ebrake.extend([false, false, false, false]);
+    assert!(ebrake.should_trigger());
+
+    clock.advance(Duration::from_secs(30));
+    assert_eq!(ebrake.state(), BrakeState::HalfOpen);
+    assert!(ebrake.trigger_on_sample(false, &Trigger::Log).is_tripped());
+    assert_eq!(ebrake.state(), BrakeState::Open { until: clock.now() + Duration::from_secs(30) });
+}
+
+#[test]
+/// Test that a brake without a cooldown stays closed and fires on every sample.
+fn it_should_stay_closed_without_a_cooldown() {
+    let mut ebrake = EBrake::new(2, 0);
+    ebrake.extend([false, false]);
+    assert!(ebrake.trigger_on_sample(false, &Trigger::Log).is_tripped());
+    assert!(ebrake.trigger_on_sample(false, &Trigger::Log).is_tripped());
+    assert_eq!(ebrake.state(), BrakeState::Closed);
+}
+
+#[test]
+/// Test that resetting closes the brake.
+fn it_should_close_on_reset() {
+    let clock = MockClock::new();
+    let mut ebrake = cooldown_brake(&clock);
+    ebrake.extend([false, false, false, false]);
+    ebrake.reset();
+    assert_eq!(ebrake.state(), BrakeState::Closed);
+    assert!(!ebrake.should_trigger());
+}
+
+#[test]
+/// Test that the recovery hook fires when a successful probe closes the brake.
+fn it_should_run_the_recovery_hook_on_close() {
+    let clock = MockClock::new();
+    let recoveries = Arc::new(AtomicUsize::new(0));
+    let recorded = Arc::clone(&recoveries);
+    let mut ebrake = cooldown_brake(&clock);
+    ebrake.set_on_recover(move || {
+        recorded.fetch_add(1, Ordering::SeqCst);
+    });
+
+    ebrake.extend([false, false, false, false]);
+    ebrake.add_sample(true);
+    assert_eq!(recoveries.load(Ordering::SeqCst), 0);
+
+    clock.advance(Duration::from_secs(30));
+    ebrake.add_sample(true);
+    assert_eq!(ebrake.state(), BrakeState::Closed);
+    assert_eq!(recoveries.load(Ordering::SeqCst), 1);
+}
+
+fn probing_brake(clock: &MockClock) -> EBrake {
+    let mut ebrake = cooldown_brake(clock);
+    ebrake.set_half_open_max_probes(3);
+    ebrake.extend([false, false, false, false]);
+    clock.advance(Duration::from_secs(30));
+    ebrake
+}
+
+#[test]
+/// Test that a half-open brake closes once all of its probes succeed.
+fn it_should_close_after_the_probes_succeed() {
+    let clock = MockClock::new();
+    let mut ebrake = probing_brake(&clock);
+    assert_eq!(ebrake.remaining_probes(), 3);
+
+    for remaining in [2, 1] {
+        assert!(!ebrake.trigger_on_sample(true, &Trigger::Log).is_tripped());
+        assert_eq!(ebrake.state(), BrakeState::HalfOpen);
+        assert_eq!(ebrake.remaining_probes(), remaining);
+    }
+
+    ebrake.add_sample(true);
+    assert_eq!(ebrake.state(), BrakeState::Closed);
+    assert_eq!(ebrake.remaining_probes(), 0);
+    assert!(ebrake.is_empty());
+}
+
+#[test]
+/// Test that a failed probe reopens the brake with a fresh cooldown and probe count.
+fn it_should_reopen_when_a_probe_fails() {
+    let clock = MockClock::new();
+    let mut ebrake = probing_brake(&clock);
+    ebrake.extend([true, true]);
+    assert!(ebrake.trigger_on_sample(false, &Trigger::Log).is_tripped());
+    assert_eq!(ebrake.state(), BrakeState::Open { until: clock.now() + Duration::from_secs(30) });
+    assert_eq!(ebrake.remaining_probes(), 0);
+
+    clock.advance(Duration::from_secs(30));
+    assert_eq!(ebrake.remaining_probes(), 3);
+}
+
+#[test]
+/// Test that a cooldown brake estimates the time and probes left until recovery.
+fn it_should_estimate_a_cooldown_recovery() {
+    let clock = MockClock::new();
+    let mut ebrake = cooldown_brake(&clock);
+    assert_eq!(ebrake.recovery_estimate(), RecoveryEstimate::Healthy);
+
+    ebrake.extend([false, false, false, false]);
+    clock.advance(Duration::from_secs(18));
+    assert_eq!(ebrake.recovery_estimate(), RecoveryEstimate::Cooldown(Duration::from_secs(12)));
+
+    clock.advance(Duration::from_secs(12));
+    assert_eq!(ebrake.recovery_estimate(), RecoveryEstimate::Probes(1));
+    ebrake.add_sample(true);
+    assert_eq!(ebrake.recovery_estimate(), RecoveryEstimate::Healthy);
+}
+
+#[test]
+/// Test that a window brake estimates the successes needed to recover.
+fn it_should_estimate_a_window_recovery() {
+    let mut ebrake = EBrake::new(10, 2);
+    ebrake.extend([true, true, false, false, false, false, false, true, true, true]);
+    assert_eq!(ebrake.recovery_estimate(), RecoveryEstimate::Samples(5));
+
+    for _ in 0..4 {
+        ebrake.add_sample(true);
+        assert!(ebrake.should_trigger());
+    }
+    assert_eq!(ebrake.recovery_estimate(), RecoveryEstimate::Samples(1));
+    ebrake.add_sample(true);
+    assert_eq!(ebrake.recovery_estimate(), RecoveryEstimate::Healthy);
+}
+
+#[test]
+/// Test that the estimate accounts for weighted failures and latching.
+fn it_should_estimate_weighted_and_latched_recoveries() {
+    let mut ebrake = EBrake::new(4, 2);
+    ebrake.add_weighted_sample(false, 5);
+    ebrake.extend([true, true, true]);
+    assert_eq!(ebrake.recovery_estimate(), RecoveryEstimate::Samples(1));
+
+    ebrake.set_latching(true);
+    ebrake.add_weighted_sample(false, 3);
+    assert_eq!(ebrake.recovery_estimate(), RecoveryEstimate::Reset);
+}
@@ -0,0 +1,87 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+use std::collections::BTreeMap;
+
+/// The BrakeRegistry collects named emergency brakes, such as one per
+/// dependency, so their overall health can be queried in one place.
+/// Brakes are kept in name order.
+/// ```
+/// use emergency_brake::*;
+/// let database = SharedEBrake::new(EBrake::new(2, 0));
+/// let mut registry = BrakeRegistry::new();
+/// registry.register("database", database.clone());
+/// registry.register("cache", SharedEBrake::new(EBrake::new(10, 3)));
+///
+/// database.add_sample(false);
+/// database.add_sample(false);
+/// assert!(registry.is_any_tripped());
+/// assert_eq!(registry.tripped(), vec!["database".to_string()]);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct BrakeRegistry {
+    brakes: BTreeMap<String, SharedEBrake>,
+}
+
+impl BrakeRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        BrakeRegistry::default()
+    }
+
+    /// Registers a brake under the given name, returning the brake previously
+    /// registered under that name, if any.
+    pub fn register(&mut self, name: &str, brake: SharedEBrake) -> Option<SharedEBrake> {
+        self.brakes.insert(name.to_string(), brake)
+    }
+
+    /// Removes the brake registered under the given name.
+    pub fn unregister(&mut self, name: &str) -> Option<SharedEBrake> {
+        self.brakes.remove(name)
+    }
+
+    /// Returns the brake registered under the given name.
+    pub fn get(&self, name: &str) -> Option<&SharedEBrake> {
+        self.brakes.get(name)
+    }
+
+    /// Returns true if any registered brake should be triggered.
+    pub fn is_any_tripped(&self) -> bool {
+        self.brakes.values().any(SharedEBrake::should_trigger)
+    }
+
+    /// Returns the names of the brakes that should be triggered.
+    pub fn tripped(&self) -> Vec<String> {
+        self.brakes
+            .iter()
+            .filter(|(_, brake)| brake.should_trigger())
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Returns a snapshot of every registered brake, by name.
+    pub fn report(&self) -> Vec<(String, BrakeSnapshot)> {
+        self.brakes
+            .iter()
+            .map(|(name, brake)| (name.clone(), brake.lock().snapshot()))
+            .collect()
+    }
+
+    /// Returns the number of registered brakes.
+    pub fn len(&self) -> usize {
+        self.brakes.len()
+    }
+
+    /// Returns true if no brakes are registered.
+    pub fn is_empty(&self) -> bool {
+        self.brakes.is_empty()
+    }
+}
+
+/// Test module for the BrakeRegistry.
+#[cfg(test)]
+mod test;
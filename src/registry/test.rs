@@ -0,0 +1,58 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+#[test]
+/// Test that the report covers every registered brake in name order.
+fn it_should_report_every_registered_brake() {
+    let (payments, search) = (SharedEBrake::new(EBrake::new(4, 1)), SharedEBrake::new(EBrake::new(2, 0)));
+    let mut registry = BrakeRegistry::new();
+    registry.register("search", search.clone());
+    registry.register("payments", payments.clone());
+    payments.add_sample(true);
+    search.add_sample(false);
+    search.add_sample(true);
+
+    let report = registry.report();
+    assert_eq!(report.len(), 2);
+    assert_eq!(report[0].0, "payments");
+    assert_eq!(report[0].1.filled, 1);
+    assert_eq!(report[0].1.successes, 1);
+    assert_eq!(report[1].0, "search");
+    assert_eq!(report[1].1.failures, 1);
+    assert!(report[1].1.tripped);
+}
+
+#[test]
+/// Test that the registry enumerates only the tripped brakes.
+fn it_should_enumerate_tripped_brakes() {
+    let mut registry = BrakeRegistry::new();
+    assert!(!registry.is_any_tripped());
+
+    for name in ["a", "b", "c"] {
+        registry.register(name, SharedEBrake::new(EBrake::new(1, 0)));
+    }
+    assert!(!registry.is_any_tripped());
+
+    registry.get("a").unwrap().add_sample(false);
+    registry.get("b").unwrap().add_sample(true);
+    registry.get("c").unwrap().add_sample(false);
+    assert!(registry.is_any_tripped());
+    assert_eq!(registry.tripped(), vec!["a".to_string(), "c".to_string()]);
+}
+
+#[test]
+/// Test that registering a name again replaces the previous brake.
+fn it_should_replace_a_brake_registered_under_the_same_name() {
+    let mut registry = BrakeRegistry::new();
+    assert!(registry.register("db", SharedEBrake::new(EBrake::new(1, 0))).is_none());
+    assert!(registry.register("db", SharedEBrake::new(EBrake::new(5, 1))).is_some());
+    assert_eq!(registry.len(), 1);
+    assert_eq!(registry.report()[0].1.window_size, 5);
+
+    assert!(registry.unregister("db").is_some());
+    assert!(registry.is_empty());
+}
@@ -0,0 +1,102 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The CrashReport configures a small report file that the emergency brake
+/// writes when it is triggered, right before the trigger action is taken, so
+/// the context of a trip survives an `Abort` or `Panic`. The report is plain
+/// text with one `key: value` line per entry.
+/// ```
+/// use emergency_brake::*;
+/// let report = CrashReport::new("/var/run/checkout/ebrake-report.txt")
+///     .metadata("version", "1.4.2")
+///     .metadata("region", "eu-west-1");
+/// let mut ebrake = EBrake::new(10, 3);
+/// ebrake.set_crash_report(report);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct CrashReport {
+    path: PathBuf,
+    metadata: Vec<(String, String)>,
+}
+
+impl CrashReport {
+    /// Creates a report written to the given path, replacing any earlier report.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        CrashReport {
+            path: path.into(),
+            metadata: Vec::new(),
+        }
+    }
+
+    /// Adds an entry of user supplied metadata to the report.
+    pub fn metadata(mut self, key: &str, value: &str) -> Self {
+        self.metadata.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Returns the path the report is written to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Renders the report for the given brake.
+    fn render(&self, ebrake: &EBrake) -> String {
+        let snapshot = ebrake.snapshot();
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        let window: String = ebrake.data.iter().map(|sample| if sample { '+' } else { '-' }).collect();
+
+        let mut report = String::from("Emergency brake crash report\n");
+        let _ = writeln!(report, "timestamp: {}.{:03}", timestamp.as_secs(), timestamp.subsec_millis());
+        if let Some(name) = ebrake.name() {
+            let _ = writeln!(report, "name: {}", name);
+        }
+        let _ = writeln!(report, "window: {}", window);
+        let _ = writeln!(report, "window_size: {}", snapshot.window_size);
+        let _ = writeln!(report, "failures: {}", snapshot.failures);
+        let _ = writeln!(report, "successes: {}", snapshot.successes);
+        let _ = writeln!(report, "failure_rate: {}", snapshot.failure_rate);
+        let _ = writeln!(report, "total_samples: {}", snapshot.total_samples);
+        let _ = writeln!(report, "total_trips: {}", snapshot.total_trips);
+        for (key, value) in &self.metadata {
+            let _ = writeln!(report, "{}: {}", key, value);
+        }
+        report
+    }
+}
+
+impl EBrake {
+    /// Sets the crash report written each time the brake is triggered. The
+    /// window is written oldest first, with `+` for a success and `-` for a
+    /// failure. A report that cannot be written is logged, and the trigger
+    /// action is still taken.
+    pub fn set_crash_report(&mut self, report: CrashReport) {
+        self.crash_report = Some(report);
+    }
+
+    /// Stops writing a crash report.
+    pub fn clear_crash_report(&mut self) {
+        self.crash_report = None;
+    }
+
+    /// Writes the crash report, if one is configured.
+    pub(crate) fn write_crash_report(&self) {
+        let Some(report) = &self.crash_report else {
+            return;
+        };
+        if let Err(err) = std::fs::write(&report.path, report.render(self)) {
+            error!(path = %report.path.display(), error = %err, "Emergency brake failed to write crash report!");
+        }
+    }
+}
+
+/// Test module for the crash report.
+#[cfg(test)]
+mod test;
@@ -0,0 +1,70 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+/// Returns a report path unique to the test in the system's temporary directory.
+fn report_path(test: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ebrake-report-{}-{}.txt", std::process::id(), test))
+}
+
+#[test]
+/// Test that a trip writes the snapshot and metadata to the report before the action is taken.
+fn it_should_write_a_report_when_triggered() {
+    let path = report_path("triggered");
+    let mut ebrake = EBrake::new(4, 1);
+    ebrake.set_name("checkout");
+    ebrake.set_crash_report(CrashReport::new(&path).metadata("version", "1.4.2"));
+    ebrake.extend([true, false, true, false]);
+    assert!(!path.exists());
+
+    assert_eq!(ebrake.trigger(&Trigger::Log), TripOutcome::Handled);
+    let report = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let lines: Vec<&str> = report.lines().collect();
+    assert_eq!(lines[0], "Emergency brake crash report");
+    assert!(lines[1].starts_with("timestamp: "));
+    assert_eq!(
+        lines[2..],
+        [
+            "name: checkout",
+            "window: +-+-",
+            "window_size: 4",
+            "failures: 2",
+            "successes: 2",
+            "failure_rate: 0.5",
+            "total_samples: 4",
+            "total_trips: 1",
+            "version: 1.4.2",
+        ]
+    );
+}
+
+#[test]
+/// Test that no report is written while the brake is not tripped, or once it is cleared.
+fn it_should_only_write_a_report_on_a_trip() {
+    let path = report_path("untripped");
+    let mut ebrake = EBrake::new(2, 0);
+    ebrake.set_crash_report(CrashReport::new(&path));
+    assert_eq!(ebrake.trigger_on_sample(true, &Trigger::Log), TripOutcome::NotTripped);
+    assert!(!path.exists());
+
+    ebrake.clear_crash_report();
+    ebrake.add_sample(false);
+    assert!(ebrake.trigger(&Trigger::Log).is_tripped());
+    assert!(!path.exists());
+}
+
+#[test]
+/// Test that a report that cannot be written does not stop the trigger action.
+fn it_should_trigger_when_the_report_cannot_be_written() {
+    let path = report_path("missing").join("report.txt");
+    let mut ebrake = EBrake::new(1, 0);
+    ebrake.set_crash_report(CrashReport::new(&path));
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.trigger(&Trigger::Log), TripOutcome::Handled);
+    assert_eq!(CrashReport::new(&path).path(), path);
+}
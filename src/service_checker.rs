@@ -0,0 +1,591 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+use async_trait::async_trait;
+use futures_util::{FutureExt, Stream};
+use std::time::Duration;
+use tokio::task::{JoinError, JoinHandle};
+use tokio_util::sync::CancellationToken;
+
+/// The ServiceCheck trait is the interface for checking or monitoring a service.
+#[cfg_attr(docsrs, doc(cfg(feature = "service_checker")))]
+#[async_trait]
+pub trait ServiceChecker {
+    /// Check if the service is running. This takes a URI as a parameter, and
+    /// performs a basic HTTP GET request to the URI. If the request succeeds with
+    /// a 2xx status, it will return true and assume the service is running, false otherwise.
+    /// The request times out after `DEFAULT_CHECK_TIMEOUT`.
+    async fn check_service_endpoint(&self, uri: &str) -> bool;
+
+    /// Similar to check_service_endpoint, but uses the supplied configuration
+    /// for the HTTP method, request headers, acceptable statuses, body matcher and timeout.
+    /// A request that does not complete within the configured timeout is
+    /// treated as a failure, as is a check that could not be made because of
+    /// the local configuration. Use `try_check_service_endpoint` to tell the
+    /// two apart.
+    async fn check_service_endpoint_with(&self, uri: &str, config: &ServiceCheckConfig) -> bool;
+
+    /// Check if a service is accepting TCP connections at the given address,
+    /// such as a database or cache that does not speak HTTP. Returns true if a
+    /// connection is established within `DEFAULT_CHECK_TIMEOUT`, false otherwise.
+    async fn check_tcp_endpoint(&self, addr: &str) -> bool;
+
+    /// Similar to check_tcp_endpoint, but uses the timeout from the supplied configuration.
+    async fn check_tcp_endpoint_with(&self, addr: &str, config: &ServiceCheckConfig) -> bool;
+
+    /// Similar to check_service_endpoint, but will check the service at a given
+    /// interval. This will spawn a background task and consume the current
+    /// instance of the EBrake. If the service stops responding, the EBrake will
+    /// be triggered with the supplied trigger action. The returned handle can
+    /// be used to stop the watcher and wait for it to exit. To keep using the
+    /// brake while it is watched, watch a clone of a `SharedEBrake` instead.
+    /// A check that cannot be made because of the local configuration, such
+    /// as an invalid URI, records no sample and stops the watcher; the error
+    /// is returned by `WatchHandle::join`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    async fn watch_service_endpoint(mut self, uri: &'static str, interval: Duration, trigger: &'static Trigger) -> WatchHandle;
+
+    /// Similar to watch_service_endpoint, but uses the supplied configuration
+    /// for each check, and retries failed checks according to its retry policy
+    /// before recording a failure.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    async fn watch_service_endpoint_with(mut self, uri: &'static str, interval: Duration, trigger: &'static Trigger, config: WatchConfig) -> WatchHandle;
+
+    /// Similar to watch_service_endpoint, but checks each of the endpoints in
+    /// turn on every tick. The results are combined into a single sample
+    /// according to the aggregation policy, which defaults to requiring all
+    /// endpoints to be healthy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    async fn watch_service_endpoints(mut self, uris: Vec<&'static str>, interval: Duration, trigger: &'static Trigger, policy: AggregationPolicy) -> WatchHandle;
+
+    /// Similar to watch_service_endpoints, but uses the supplied watcher configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    async fn watch_service_endpoints_with(mut self, uris: Vec<&'static str>, interval: Duration, trigger: &'static Trigger, policy: AggregationPolicy, config: WatchConfig) -> WatchHandle;
+
+    /// Similar to watch_service_endpoint, but takes the interval in whole seconds.
+    #[deprecated(note = "use watch_service_endpoint with a Duration interval")]
+    async fn watch_service_endpoint_secs(self, uri: &'static str, interval: usize, trigger: &'static Trigger) -> WatchHandle
+    where
+        Self: Sized + Send + 'static,
+    {
+        self.watch_service_endpoint(uri, Duration::from_secs(interval as u64), trigger).await
+    }
+}
+
+/// The RetryPolicy controls how often a failed check is retried by a watcher
+/// before a failure is recorded. Retries back off exponentially, doubling the
+/// delay after each attempt, starting at `base_delay` and capped at `max_delay`.
+#[cfg_attr(docsrs, doc(cfg(feature = "service_checker")))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// The number of times a failed check is retried.
+    pub max_retries: usize,
+
+    /// The delay before the first retry.
+    pub base_delay: Duration,
+
+    /// The longest delay between two retries.
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Returns the delay before the given zero-based retry.
+    fn delay(&self, retry: usize) -> Duration {
+        let factor = 1u32.checked_shl(retry as u32).unwrap_or(u32::MAX);
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+/// The AggregationPolicy decides whether the results of checking several
+/// endpoints count as a successful sample.
+#[cfg_attr(docsrs, doc(cfg(feature = "service_checker")))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AggregationPolicy {
+    /// The sample succeeds if any endpoint is healthy.
+    Any,
+
+    /// The sample succeeds only if every endpoint is healthy.
+    #[default]
+    All,
+
+    /// The sample succeeds if at least the given number of endpoints are healthy.
+    Quorum(usize),
+}
+
+impl AggregationPolicy {
+    /// Returns true if `healthy` of `total` endpoints make a successful sample.
+    fn combine(&self, healthy: usize, total: usize) -> bool {
+        match self {
+            AggregationPolicy::Any => healthy > 0,
+            AggregationPolicy::All => healthy == total,
+            AggregationPolicy::Quorum(quorum) => healthy >= *quorum,
+        }
+    }
+}
+
+/// The WatchConfig configures a background service watcher.
+#[cfg_attr(docsrs, doc(cfg(feature = "service_checker")))]
+#[derive(Clone, Debug, Default)]
+pub struct WatchConfig {
+    /// The configuration used for each check.
+    pub check: ServiceCheckConfig,
+
+    /// The policy for retrying failed checks. When `None`, every failed check
+    /// is recorded as a failure.
+    pub retry: Option<RetryPolicy>,
+
+    /// The fraction of the interval, between `0.0` and `1.0`, by which each
+    /// tick may be randomly delayed. This keeps many instances watching the
+    /// same endpoint from polling it in lockstep. Values outside the range are
+    /// clamped.
+    pub jitter: f64,
+
+    /// The seed for the jitter, so that tick offsets are reproducible. When
+    /// `None`, a random seed is chosen for each watcher.
+    pub jitter_seed: Option<u64>,
+}
+
+/// A small SplitMix64 generator for watcher jitter.
+#[derive(Clone, Debug)]
+struct JitterRng(u64);
+
+impl JitterRng {
+    fn new(seed: Option<u64>) -> Self {
+        use std::hash::{BuildHasher, Hasher};
+        JitterRng(seed.unwrap_or_else(|| std::collections::hash_map::RandomState::new().build_hasher().finish()))
+    }
+
+    /// Returns a uniformly distributed value in `[0.0, 1.0)`.
+    fn next_f64(&mut self) -> f64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns the gap until the next tick, delayed by up to `jitter` of the interval.
+    fn gap(&mut self, interval: Duration, jitter: f64) -> Duration {
+        interval + interval.mul_f64(jitter.clamp(0.0, 1.0) * self.next_f64())
+    }
+}
+
+/// The WatchHandle controls a background service watcher.
+/// Dropping the handle does not stop the watcher.
+#[cfg_attr(docsrs, doc(cfg(feature = "service_checker")))]
+#[derive(Debug)]
+pub struct WatchHandle {
+    handle: JoinHandle<Result<(), ServiceCheckError>>,
+    token: CancellationToken,
+}
+
+impl WatchHandle {
+    /// Signals the watcher to stop after its current check.
+    pub fn cancel(&self) {
+        self.token.cancel();
+    }
+
+    /// Returns a token that cancels the watcher, for integrating with other shutdown logic.
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+
+    /// Returns true once the watcher task has exited.
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    /// Waits for the watcher task to exit. The inner result is the error that
+    /// stopped the watcher, if its checks could not be made because of the
+    /// local configuration.
+    pub async fn join(self) -> Result<Result<(), ServiceCheckError>, JoinError> {
+        self.handle.await
+    }
+}
+
+impl EBrake {
+    /// Similar to check_service_endpoint_with, but returns the category of a
+    /// failed check, or `None` if the service is running. A host name that
+    /// cannot be resolved is reported as `ErrorKind::Dns`, separately from a
+    /// refused or reset connection, so the result can be recorded with
+    /// `add_categorized_sample`.
+    /// ```no_run
+    /// use emergency_brake::*;
+    /// # async fn check(mut ebrake: EBrake) {
+    /// let config = ServiceCheckConfig::default();
+    /// let failure = ebrake.classify_service_endpoint("http://payments.internal/health", &config).await;
+    /// ebrake.add_categorized_sample(failure);
+    /// # }
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "service_checker")))]
+    pub async fn classify_service_endpoint(&self, uri: &str, config: &ServiceCheckConfig) -> Option<ErrorKind> {
+        self.try_classify_service_endpoint(uri, config).await.unwrap_or(Some(ErrorKind::Other))
+    }
+
+    /// Similar to classify_service_endpoint, but returns an error if the check
+    /// could not be made because of the local configuration, such as an
+    /// invalid proxy or URI, instead of reporting it as a failed check. This
+    /// keeps a misconfiguration from tripping the brake.
+    /// ```no_run
+    /// use emergency_brake::*;
+    /// # async fn check(mut ebrake: EBrake) {
+    /// let config = ServiceCheckConfig::default();
+    /// match ebrake.try_check_service_endpoint("http://payments.internal/health", &config).await {
+    ///     Ok(healthy) => ebrake.add_sample(healthy),
+    ///     Err(error) => eprintln!("health check is misconfigured: {}", error),
+    /// }
+    /// # }
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "service_checker")))]
+    pub async fn try_classify_service_endpoint(&self, uri: &str, config: &ServiceCheckConfig) -> Result<Option<ErrorKind>, ServiceCheckError> {
+        try_classify_http(&config.client(self.http_client())?, uri, config).await
+    }
+
+    /// Similar to check_service_endpoint_with, but returns an error if the
+    /// check could not be made because of the local configuration, like
+    /// try_classify_service_endpoint.
+    #[cfg_attr(docsrs, doc(cfg(feature = "service_checker")))]
+    pub async fn try_check_service_endpoint(&self, uri: &str, config: &ServiceCheckConfig) -> Result<bool, ServiceCheckError> {
+        Ok(self.try_classify_service_endpoint(uri, config).await?.is_none())
+    }
+
+    /// Sets the HTTP client used for service checks, for example to configure
+    /// TLS settings, proxies or custom root certificates. The client is reused
+    /// across checks, so its connection pool is shared.
+    pub fn set_http_client(&mut self, client: reqwest::Client) {
+        self.http_client = std::sync::OnceLock::from(client);
+    }
+
+    /// Returns the HTTP client used for service checks, creating a default
+    /// client on first use if none has been set.
+    pub fn http_client(&self) -> &reqwest::Client {
+        self.http_client.get_or_init(reqwest::Client::new)
+    }
+}
+
+/// Checks an HTTP endpoint with the given client and configuration. A request
+/// that could not be built is returned as an error.
+async fn check_http(client: &reqwest::Client, uri: &str, config: &ServiceCheckConfig) -> Result<bool, ServiceCheckError> {
+    Ok(try_classify_http(client, uri, config).await?.is_none())
+}
+
+/// Checks an HTTP endpoint, returning the category of the failure, or `None`
+/// if it is healthy. A request that could not be built is returned as an error.
+async fn try_classify_http(client: &reqwest::Client, uri: &str, config: &ServiceCheckConfig) -> Result<Option<ErrorKind>, ServiceCheckError> {
+    let response = client
+        .request(config.method.clone(), uri)
+        .headers(config.headers.clone())
+        .timeout(config.timeout)
+        .send()
+        .await;
+    let response = match response {
+        Ok(response) if config.accepts_redirect(response.status()) => return Ok(None),
+        Ok(response) if config.accepts(response.status()) => response,
+        Ok(response) => return Ok(Some(check_config::status_error_kind(response.status()))),
+        Err(error) => return check_config::request_failure(error).map(Some),
+    };
+    match &config.body_matcher {
+        // A body reporting a degraded state is a failure of the service itself.
+        Some(matcher) => match response.text().await {
+            Ok(body) => Ok((!matcher.matches(&body)).then_some(ErrorKind::ServerError)),
+            Err(error) => Ok(Some(check_config::request_error_kind(&error))),
+        },
+        None => Ok(None),
+    }
+}
+
+/// Checks that a TCP connection can be established within the configured timeout.
+async fn check_tcp(addr: &str, config: &ServiceCheckConfig) -> bool {
+    let connect = tokio::net::TcpStream::connect(addr);
+    matches!(tokio::time::timeout(config.timeout, connect).await, Ok(Ok(_)))
+}
+
+/// Runs the check, retrying a failed check according to the retry policy.
+/// Returns false only once every attempt has failed.
+pub(crate) async fn retry_check<F: std::future::Future<Output = bool>>(retry: Option<&RetryPolicy>, mut check: impl FnMut() -> F) -> bool {
+    let checked = try_retry_check(retry, || check().map(Ok::<_, std::convert::Infallible>)).await;
+    match checked {
+        Ok(healthy) => healthy,
+        Err(never) => match never {},
+    }
+}
+
+/// Similar to retry_check, but stops at the first error without retrying,
+/// since an error is not a failed check.
+async fn try_retry_check<E, F: std::future::Future<Output = Result<bool, E>>>(retry: Option<&RetryPolicy>, mut check: impl FnMut() -> F) -> Result<bool, E> {
+    if check().await? {
+        return Ok(true);
+    }
+    let Some(retry) = retry else {
+        return Ok(false);
+    };
+    for attempt in 0..retry.max_retries {
+        tokio::time::sleep(retry.delay(attempt)).await;
+        if check().await? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Checks the endpoint, retrying a failed check according to the retry policy.
+async fn check_with_retry(client: &reqwest::Client, uri: &str, config: &WatchConfig) -> Result<bool, ServiceCheckError> {
+    try_retry_check(config.retry.as_ref(), || check_http(client, uri, &config.check)).await
+}
+
+/// Checks every endpoint and combines the results according to the policy.
+async fn check_endpoints(client: &reqwest::Client, uris: &[&str], policy: AggregationPolicy, config: &WatchConfig) -> Result<bool, ServiceCheckError> {
+    let mut healthy = 0;
+    for uri in uris {
+        if check_with_retry(client, uri, config).await? {
+            healthy += 1;
+        }
+    }
+    Ok(policy.combine(healthy, uris.len()))
+}
+
+/// Spawns a watcher that checks the endpoints on every tick and passes each
+/// aggregated result to `record`. If the HTTP client could not be built, or a
+/// check could not be made, the watcher logs the error and exits with it
+/// without recording a sample.
+fn spawn_watcher(
+    client: Result<reqwest::Client, ServiceCheckError>,
+    uris: Vec<&'static str>,
+    interval: Duration,
+    policy: AggregationPolicy,
+    config: WatchConfig,
+    mut record: impl FnMut(bool) + Send + 'static,
+) -> WatchHandle {
+    assert!(!interval.is_zero(), "watch interval must be non-zero");
+    let token = CancellationToken::new();
+    let cancelled = token.clone();
+    let handle = tokio::spawn(async move {
+        let client = client.inspect_err(|error| error!(%error, "Service watcher could not start"))?;
+        let mut rng = JitterRng::new(config.jitter_seed);
+        let mut next = tokio::time::Instant::now();
+        loop {
+            tokio::select! {
+                _ = cancelled.cancelled() => break,
+                _ = tokio::time::sleep_until(next) => {
+                    next += rng.gap(interval, config.jitter);
+                    let healthy = check_endpoints(&client, &uris, policy, &config).await;
+                    record(healthy.inspect_err(|error| error!(%error, "Service watcher stopped"))?);
+                }
+            }
+        }
+        Ok::<_, ServiceCheckError>(())
+    });
+
+    WatchHandle { handle, token }
+}
+
+/// Returns a stream that checks the endpoint on every tick and yields each
+/// result. If the HTTP client could not be built, or a check could not be
+/// made, the stream yields the error and ends.
+fn watch_stream(client: Result<reqwest::Client, ServiceCheckError>, uri: &'static str, interval: Duration, config: WatchConfig) -> impl Stream<Item = Result<bool, ServiceCheckError>> + Send {
+    assert!(!interval.is_zero(), "watch interval must be non-zero");
+    let rng = JitterRng::new(config.jitter_seed);
+    let next = tokio::time::Instant::now();
+    // The state is the next check to make, the error to yield, or none once the stream has ended.
+    let state = Some(client.map(|client| (client, config, rng, next)));
+    futures_util::stream::unfold(state, move |state| async move {
+        let (client, config, mut rng, next) = match state? {
+            Ok(checking) => checking,
+            Err(error) => return Some((Err(error), None)),
+        };
+        tokio::time::sleep_until(next).await;
+        let next = next + rng.gap(interval, config.jitter);
+        match check_with_retry(&client, uri, &config).await {
+            Ok(healthy) => Some((Ok(healthy), Some(Ok((client, config, rng, next))))),
+            Err(error) => Some((Err(error), None)),
+        }
+    })
+}
+
+impl EBrake {
+    /// Returns a stream that checks the endpoint on every tick and yields each
+    /// result, without spawning a task or recording any samples. This leaves
+    /// the lifecycle and the handling of each result to the caller, for
+    /// example inside its own `select!` loop. The stream ends when dropped.
+    /// A check that cannot be made because of the local configuration yields
+    /// the error and ends the stream.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    /// ```no_run
+    /// use emergency_brake::*;
+    /// use futures_util::StreamExt;
+    /// use std::time::Duration;
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let mut ebrake = EBrake::new(10, 3);
+    /// let checks = ebrake.watch_stream("http://localhost:8080/health", Duration::from_secs(5));
+    /// let mut checks = std::pin::pin!(checks);
+    /// while let Some(healthy) = checks.next().await {
+    ///     match healthy {
+    ///         Ok(healthy) => ebrake.trigger_on_sample(healthy, &Trigger::Log),
+    ///         Err(error) => panic!("health check is misconfigured: {}", error),
+    ///     };
+    /// }
+    /// # });
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "service_checker")))]
+    pub fn watch_stream(&self, uri: &'static str, interval: Duration) -> impl Stream<Item = Result<bool, ServiceCheckError>> + Send + 'static {
+        self.watch_stream_with(uri, interval, WatchConfig::default())
+    }
+
+    /// Similar to watch_stream, but uses the supplied watcher configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    #[cfg_attr(docsrs, doc(cfg(feature = "service_checker")))]
+    pub fn watch_stream_with(&self, uri: &'static str, interval: Duration, config: WatchConfig) -> impl Stream<Item = Result<bool, ServiceCheckError>> + Send + 'static {
+        let client = config.check.client(self.http_client());
+        watch_stream(client, uri, interval, config)
+    }
+}
+
+impl SharedEBrake {
+    /// Similar to `EBrake::classify_service_endpoint`, using the HTTP client set on the EBrake.
+    #[cfg_attr(docsrs, doc(cfg(feature = "service_checker")))]
+    pub async fn classify_service_endpoint(&self, uri: &str, config: &ServiceCheckConfig) -> Option<ErrorKind> {
+        self.try_classify_service_endpoint(uri, config).await.unwrap_or(Some(ErrorKind::Other))
+    }
+
+    /// Similar to `EBrake::try_classify_service_endpoint`, using the HTTP client set on the EBrake.
+    #[cfg_attr(docsrs, doc(cfg(feature = "service_checker")))]
+    pub async fn try_classify_service_endpoint(&self, uri: &str, config: &ServiceCheckConfig) -> Result<Option<ErrorKind>, ServiceCheckError> {
+        // The lock is not held across the request.
+        let client = config.client(self.lock().http_client())?;
+        try_classify_http(&client, uri, config).await
+    }
+
+    /// Similar to `EBrake::try_check_service_endpoint`, using the HTTP client set on the EBrake.
+    #[cfg_attr(docsrs, doc(cfg(feature = "service_checker")))]
+    pub async fn try_check_service_endpoint(&self, uri: &str, config: &ServiceCheckConfig) -> Result<bool, ServiceCheckError> {
+        Ok(self.try_classify_service_endpoint(uri, config).await?.is_none())
+    }
+
+    /// Similar to `EBrake::watch_stream`, using the HTTP client set on the EBrake.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    #[cfg_attr(docsrs, doc(cfg(feature = "service_checker")))]
+    pub fn watch_stream(&self, uri: &'static str, interval: Duration) -> impl Stream<Item = Result<bool, ServiceCheckError>> + Send + 'static {
+        self.watch_stream_with(uri, interval, WatchConfig::default())
+    }
+
+    /// Similar to `EBrake::watch_stream_with`, using the HTTP client set on the EBrake.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    #[cfg_attr(docsrs, doc(cfg(feature = "service_checker")))]
+    pub fn watch_stream_with(&self, uri: &'static str, interval: Duration, config: WatchConfig) -> impl Stream<Item = Result<bool, ServiceCheckError>> + Send + 'static {
+        let client = config.check.client(self.lock().http_client());
+        watch_stream(client, uri, interval, config)
+    }
+}
+
+#[async_trait]
+impl ServiceChecker for EBrake {
+    async fn check_service_endpoint(&self, uri: &str) -> bool {
+        self.check_service_endpoint_with(uri, &ServiceCheckConfig::default()).await
+    }
+
+    async fn check_service_endpoint_with(&self, uri: &str, config: &ServiceCheckConfig) -> bool {
+        self.try_check_service_endpoint(uri, config).await.unwrap_or(false)
+    }
+
+    async fn check_tcp_endpoint(&self, addr: &str) -> bool {
+        self.check_tcp_endpoint_with(addr, &ServiceCheckConfig::default()).await
+    }
+
+    async fn check_tcp_endpoint_with(&self, addr: &str, config: &ServiceCheckConfig) -> bool {
+        check_tcp(addr, config).await
+    }
+
+    async fn watch_service_endpoint(self, uri: &'static str, interval: Duration, trigger: &'static Trigger) -> WatchHandle {
+        self.watch_service_endpoint_with(uri, interval, trigger, WatchConfig::default()).await
+    }
+
+    async fn watch_service_endpoint_with(self, uri: &'static str, interval: Duration, trigger: &'static Trigger, config: WatchConfig) -> WatchHandle {
+        self.watch_service_endpoints_with(vec![uri], interval, trigger, AggregationPolicy::All, config).await
+    }
+
+    async fn watch_service_endpoints(self, uris: Vec<&'static str>, interval: Duration, trigger: &'static Trigger, policy: AggregationPolicy) -> WatchHandle {
+        self.watch_service_endpoints_with(uris, interval, trigger, policy, WatchConfig::default()).await
+    }
+
+    async fn watch_service_endpoints_with(mut self, uris: Vec<&'static str>, interval: Duration, trigger: &'static Trigger, policy: AggregationPolicy, config: WatchConfig) -> WatchHandle {
+        let client = config.check.client(self.http_client());
+        spawn_watcher(client, uris, interval, policy, config, move |result| {
+            self.trigger_on_sample(result, trigger);
+        })
+    }
+}
+
+/// A SharedEBrake can be watched without giving it up: the watcher records
+/// its samples through a clone, so the rest of the application can keep
+/// recording samples and inspecting the window while the watcher runs. The
+/// watcher uses the HTTP client set on the EBrake when it starts.
+#[async_trait]
+impl ServiceChecker for SharedEBrake {
+    async fn check_service_endpoint(&self, uri: &str) -> bool {
+        self.check_service_endpoint_with(uri, &ServiceCheckConfig::default()).await
+    }
+
+    async fn check_service_endpoint_with(&self, uri: &str, config: &ServiceCheckConfig) -> bool {
+        self.try_check_service_endpoint(uri, config).await.unwrap_or(false)
+    }
+
+    async fn check_tcp_endpoint(&self, addr: &str) -> bool {
+        self.check_tcp_endpoint_with(addr, &ServiceCheckConfig::default()).await
+    }
+
+    async fn check_tcp_endpoint_with(&self, addr: &str, config: &ServiceCheckConfig) -> bool {
+        check_tcp(addr, config).await
+    }
+
+    async fn watch_service_endpoint(self, uri: &'static str, interval: Duration, trigger: &'static Trigger) -> WatchHandle {
+        self.watch_service_endpoint_with(uri, interval, trigger, WatchConfig::default()).await
+    }
+
+    async fn watch_service_endpoint_with(self, uri: &'static str, interval: Duration, trigger: &'static Trigger, config: WatchConfig) -> WatchHandle {
+        self.watch_service_endpoints_with(vec![uri], interval, trigger, AggregationPolicy::All, config).await
+    }
+
+    async fn watch_service_endpoints(self, uris: Vec<&'static str>, interval: Duration, trigger: &'static Trigger, policy: AggregationPolicy) -> WatchHandle {
+        self.watch_service_endpoints_with(uris, interval, trigger, policy, WatchConfig::default()).await
+    }
+
+    async fn watch_service_endpoints_with(self, uris: Vec<&'static str>, interval: Duration, trigger: &'static Trigger, policy: AggregationPolicy, config: WatchConfig) -> WatchHandle {
+        let client = config.check.client(self.lock().http_client());
+        spawn_watcher(client, uris, interval, policy, config, move |result| {
+            self.trigger_on_sample(result, trigger);
+        })
+    }
+}
+
+/// Test module for the ServiceChecker.
+#[cfg(test)]
+mod test;
@@ -0,0 +1,791 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+use reqwest::Method;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// A canned HTTP response served by the MockServer.
+#[derive(Clone, Debug)]
+struct MockResponse {
+    status: u16,
+    body: &'static str,
+    delay: Duration,
+    location: Option<&'static str>,
+}
+
+impl MockResponse {
+    fn status(status: u16) -> Self {
+        MockResponse {
+            status,
+            body: "",
+            delay: Duration::ZERO,
+            location: None,
+        }
+    }
+
+    fn redirect(location: &'static str) -> Self {
+        MockResponse {
+            location: Some(location),
+            ..MockResponse::status(302)
+        }
+    }
+}
+
+/// A minimal HTTP server that answers each request with the response chosen by its handler.
+struct MockServer {
+    address: String,
+    requests: Arc<Mutex<Vec<String>>>,
+    hits: Arc<AtomicUsize>,
+}
+
+impl MockServer {
+    /// Starts the server. The handler receives the zero-based request number and the raw request head.
+    async fn start(handler: impl Fn(usize, &str) -> MockResponse + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let hits = Arc::new(AtomicUsize::new(0));
+        let handler = Arc::new(handler);
+
+        let (recorded, counter) = (Arc::clone(&requests), Arc::clone(&hits));
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let (recorded, counter, handler) = (Arc::clone(&recorded), Arc::clone(&counter), Arc::clone(&handler));
+                tokio::spawn(async move {
+                    let mut request = Vec::new();
+                    let mut buffer = [0; 1024];
+                    while !request.ends_with(b"\r\n\r\n") {
+                        match stream.read(&mut buffer).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(read) => request.extend_from_slice(&buffer[..read]),
+                        }
+                    }
+                    let request = String::from_utf8_lossy(&request).into_owned();
+                    let response = handler(counter.fetch_add(1, Ordering::SeqCst), &request);
+                    recorded.lock().unwrap().push(request);
+
+                    tokio::time::sleep(response.delay).await;
+                    let location = response.location.map(|location| format!("Location: {}\r\n", location)).unwrap_or_default();
+                    let reply = format!(
+                        "HTTP/1.1 {} Mock\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        response.status,
+                        location,
+                        response.body.len(),
+                        response.body,
+                    );
+                    let _ = stream.write_all(reply.as_bytes()).await;
+                });
+            }
+        });
+
+        MockServer { address, requests, hits }
+    }
+
+    fn uri(&self, path: &str) -> String {
+        format!("http://{}{}", self.address, path)
+    }
+
+    fn hits(&self) -> usize {
+        self.hits.load(Ordering::SeqCst)
+    }
+
+    fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+#[tokio::test]
+/// Test that a responding endpoint is reported as running.
+async fn it_should_check_a_running_endpoint() {
+    let server = MockServer::start(|_, _| MockResponse::status(200)).await;
+    let ebrake = EBrake::new(10, 3);
+    assert!(ebrake.check_service_endpoint(&server.uri("/")).await);
+    assert_eq!(server.hits(), 1);
+}
+
+#[tokio::test]
+/// Test that only 2xx statuses are treated as a running service by default.
+async fn it_should_require_a_successful_status() {
+    let ebrake = EBrake::new(10, 3);
+    for (status, expected) in [(200, true), (204, true), (503, false), (404, false)] {
+        let server = MockServer::start(move |_, _| MockResponse::status(status)).await;
+        assert_eq!(ebrake.check_service_endpoint(&server.uri("/")).await, expected, "status {}", status);
+    }
+}
+
+#[tokio::test]
+/// Test that a configured set of acceptable statuses replaces the 2xx default.
+async fn it_should_accept_configured_statuses() {
+    let config = ServiceCheckConfig {
+        acceptable_statuses: Some(vec![200, 404]),
+        ..ServiceCheckConfig::default()
+    };
+    let ebrake = EBrake::new(10, 3);
+    for (status, expected) in [(200, true), (404, true), (204, false), (503, false)] {
+        let server = MockServer::start(move |_, _| MockResponse::status(status)).await;
+        assert_eq!(ebrake.check_service_endpoint_with(&server.uri("/"), &config).await, expected, "status {}", status);
+    }
+}
+
+#[tokio::test]
+/// Test that a HEAD probe is sent to the configured health check path.
+async fn it_should_probe_with_a_configured_method() {
+    let server = MockServer::start(|_, request| match request.starts_with("HEAD /healthz ") {
+        true => MockResponse::status(200),
+        false => MockResponse::status(405),
+    })
+    .await;
+    let config = ServiceCheckConfig {
+        method: Method::HEAD,
+        ..ServiceCheckConfig::default()
+    };
+    let ebrake = EBrake::new(10, 3);
+    assert!(ebrake.check_service_endpoint_with(&server.uri("/healthz"), &config).await);
+    assert!(!ebrake.check_service_endpoint(&server.uri("/healthz")).await);
+}
+
+#[tokio::test]
+/// Test that configured headers are sent with the request.
+async fn it_should_send_configured_headers() {
+    let server = MockServer::start(|_, request| match request.to_lowercase().contains("authorization: bearer secret") {
+        true => MockResponse::status(200),
+        false => MockResponse::status(401),
+    })
+    .await;
+    let mut config = ServiceCheckConfig::default();
+    config.headers.insert(reqwest::header::AUTHORIZATION, "Bearer secret".parse().unwrap());
+    let ebrake = EBrake::new(10, 3);
+    assert!(ebrake.check_service_endpoint_with(&server.uri("/"), &config).await);
+    assert!(!ebrake.check_service_endpoint(&server.uri("/")).await);
+    assert!(server.requests()[0].starts_with("GET / "));
+}
+
+#[tokio::test]
+/// Test that an endpoint that responds after the timeout is reported as failing.
+async fn it_should_fail_a_check_that_times_out() {
+    let server = MockServer::start(|_, _| MockResponse {
+        delay: Duration::from_secs(5),
+        ..MockResponse::status(200)
+    })
+    .await;
+    let config = ServiceCheckConfig {
+        timeout: Duration::from_millis(100),
+        ..ServiceCheckConfig::default()
+    };
+    let ebrake = EBrake::new(10, 3);
+    assert!(!ebrake.check_service_endpoint_with(&server.uri("/"), &config).await);
+}
+
+#[tokio::test]
+/// Test that an endpoint that responds within the timeout is reported as running.
+async fn it_should_pass_a_check_within_the_timeout() {
+    let server = MockServer::start(|_, _| MockResponse {
+        delay: Duration::from_millis(10),
+        ..MockResponse::status(200)
+    })
+    .await;
+    let config = ServiceCheckConfig {
+        timeout: Duration::from_secs(5),
+        ..ServiceCheckConfig::default()
+    };
+    let ebrake = EBrake::new(10, 3);
+    assert!(ebrake.check_service_endpoint_with(&server.uri("/"), &config).await);
+}
+
+#[tokio::test]
+/// Test that an injected HTTP client is used for checks.
+async fn it_should_use_an_injected_client() {
+    let server = MockServer::start(|_, request| match request.to_lowercase().contains("x-injected: yes") {
+        true => MockResponse::status(200),
+        false => MockResponse::status(400),
+    })
+    .await;
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("x-injected", "yes".parse().unwrap());
+    let client = reqwest::Client::builder().default_headers(headers).build().unwrap();
+
+    let ebrake = EBrake::new(10, 3);
+    assert!(!ebrake.check_service_endpoint(&server.uri("/")).await);
+
+    let ebrake = EBrake::builder().window_size(10).failure_threshold(3).http_client(client).build().unwrap();
+    assert!(ebrake.check_service_endpoint(&server.uri("/")).await);
+    assert!(ebrake.check_service_endpoint(&server.uri("/")).await);
+}
+
+#[tokio::test]
+/// Test that a listening TCP socket is reported as running.
+async fn it_should_connect_to_a_listening_socket() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    let ebrake = EBrake::new(10, 3);
+    assert!(ebrake.check_tcp_endpoint(&addr).await);
+}
+
+#[tokio::test]
+/// Test that a closed TCP port is reported as failing.
+async fn it_should_fail_to_connect_to_a_closed_port() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    drop(listener);
+    let ebrake = EBrake::new(10, 3);
+    assert!(!ebrake.check_tcp_endpoint(&addr).await);
+}
+
+#[tokio::test]
+/// Test that a cancelled watcher stops checking the endpoint and exits.
+async fn it_should_stop_a_cancelled_watcher() {
+    let server = MockServer::start(|_, _| MockResponse::status(200)).await;
+    let uri: &'static str = Box::leak(server.uri("/").into_boxed_str());
+    let handle = EBrake::new(10, 3).watch_service_endpoint(uri, Duration::from_secs(1), &Trigger::Log).await;
+
+    while server.hits() == 0 {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert!(!handle.is_finished());
+
+    handle.cancel();
+    tokio::time::timeout(Duration::from_secs(5), handle.join()).await.unwrap().unwrap().unwrap();
+    assert_eq!(server.hits(), 1);
+}
+
+#[tokio::test]
+/// Test that the watcher polls at sub-second intervals.
+async fn it_should_poll_at_a_sub_second_interval() {
+    let server = MockServer::start(|_, _| MockResponse::status(200)).await;
+    let uri: &'static str = Box::leak(server.uri("/").into_boxed_str());
+    let handle = EBrake::new(10, 3).watch_service_endpoint(uri, Duration::from_millis(100), &Trigger::Log).await;
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while server.hits() < 4 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+    handle.cancel();
+    handle.join().await.unwrap().unwrap();
+}
+
+#[tokio::test]
+#[allow(deprecated)]
+/// Test that the seconds-based watcher shim still starts a watcher.
+async fn it_should_watch_with_the_seconds_shim() {
+    let server = MockServer::start(|_, _| MockResponse::status(200)).await;
+    let uri: &'static str = Box::leak(server.uri("/").into_boxed_str());
+    let handle = EBrake::new(10, 3).watch_service_endpoint_secs(uri, 1, &Trigger::Log).await;
+    while server.hits() == 0 {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    handle.cancel();
+    handle.join().await.unwrap().unwrap();
+}
+
+/// Returns a retry policy with short delays suitable for tests.
+fn quick_retries(max_retries: usize) -> RetryPolicy {
+    RetryPolicy {
+        max_retries,
+        base_delay: Duration::from_millis(5),
+        max_delay: Duration::from_millis(20),
+    }
+}
+
+#[test]
+/// Test that the retry delay doubles up to the configured maximum.
+fn it_should_back_off_exponentially() {
+    let retry = RetryPolicy {
+        max_retries: 10,
+        base_delay: Duration::from_millis(100),
+        max_delay: Duration::from_secs(1),
+    };
+    assert_eq!(retry.delay(0), Duration::from_millis(100));
+    assert_eq!(retry.delay(1), Duration::from_millis(200));
+    assert_eq!(retry.delay(3), Duration::from_millis(800));
+    assert_eq!(retry.delay(4), Duration::from_secs(1));
+    assert_eq!(retry.delay(64), Duration::from_secs(1));
+}
+
+#[tokio::test]
+/// Test that a check which fails twice and then succeeds is recorded as a success.
+async fn it_should_record_a_success_after_retries() {
+    let server = MockServer::start(|hit, _| MockResponse::status(if hit < 2 { 503 } else { 200 })).await;
+    let config = WatchConfig {
+        retry: Some(quick_retries(2)),
+        ..WatchConfig::default()
+    };
+    assert!(check_with_retry(&reqwest::Client::new(), &server.uri("/"), &config).await.unwrap());
+    assert_eq!(server.hits(), 3);
+}
+
+#[tokio::test]
+/// Test that a failure is only recorded once every retry has failed.
+async fn it_should_record_a_failure_when_retries_are_exhausted() {
+    let server = MockServer::start(|_, _| MockResponse::status(503)).await;
+    let config = WatchConfig {
+        retry: Some(quick_retries(2)),
+        ..WatchConfig::default()
+    };
+    assert!(!check_with_retry(&reqwest::Client::new(), &server.uri("/"), &config).await.unwrap());
+    assert_eq!(server.hits(), 3);
+}
+
+static RETRY_TRIPS: AtomicUsize = AtomicUsize::new(0);
+
+fn record_retry_trip() {
+    RETRY_TRIPS.fetch_add(1, Ordering::SeqCst);
+}
+
+#[tokio::test]
+/// Test that a watcher does not trip on failures that succeed when retried.
+async fn it_should_not_trip_a_watcher_on_a_transient_failure() {
+    static TRIGGER: Trigger = Trigger::Callback(record_retry_trip);
+    let server = MockServer::start(|hit, _| MockResponse::status(if hit % 2 == 0 { 503 } else { 200 })).await;
+    let uri: &'static str = Box::leak(server.uri("/").into_boxed_str());
+    let config = WatchConfig {
+        retry: Some(quick_retries(1)),
+        ..WatchConfig::default()
+    };
+    let handle = EBrake::new(1, 0).watch_service_endpoint_with(uri, Duration::from_millis(50), &TRIGGER, config).await;
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while server.hits() < 6 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+    handle.cancel();
+    handle.join().await.unwrap().unwrap();
+    assert_eq!(RETRY_TRIPS.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+/// Test that the jitter is reproducible for a given seed and stays within range.
+fn it_should_jitter_within_the_configured_range() {
+    let interval = Duration::from_millis(100);
+    let (mut first, mut second) = (JitterRng::new(Some(7)), JitterRng::new(Some(7)));
+    for _ in 0..100 {
+        let gap = first.gap(interval, 0.5);
+        assert_eq!(gap, second.gap(interval, 0.5));
+        assert!(gap >= interval && gap < Duration::from_millis(150), "gap {:?} out of range", gap);
+    }
+    assert_eq!(first.gap(interval, 0.0), interval);
+    assert!(first.gap(interval, 4.0) < Duration::from_millis(200));
+}
+
+#[tokio::test]
+/// Test that consecutive watcher ticks are spaced within the jittered range.
+async fn it_should_space_jittered_ticks_within_the_range() {
+    let arrivals = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&arrivals);
+    let server = MockServer::start(move |_, _| {
+        recorded.lock().unwrap().push(std::time::Instant::now());
+        MockResponse::status(200)
+    })
+    .await;
+    let uri: &'static str = Box::leak(server.uri("/").into_boxed_str());
+    let config = WatchConfig {
+        jitter: 0.5,
+        jitter_seed: Some(42),
+        ..WatchConfig::default()
+    };
+    let handle = EBrake::new(10, 3).watch_service_endpoint_with(uri, Duration::from_millis(100), &Trigger::Log, config).await;
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while server.hits() < 5 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+    handle.cancel();
+    handle.join().await.unwrap().unwrap();
+
+    // Individual requests can be delayed by the scheduler, so check the
+    // average spacing rather than each gap.
+    let arrivals = arrivals.lock().unwrap();
+    let span = arrivals[4] - arrivals[0];
+    assert!(span >= Duration::from_millis(340) && span < Duration::from_millis(700), "span {:?} out of range", span);
+}
+
+#[test]
+/// Test that each aggregation policy combines endpoint results as documented.
+fn it_should_combine_endpoint_results() {
+    assert!(AggregationPolicy::Any.combine(1, 3));
+    assert!(!AggregationPolicy::Any.combine(0, 3));
+    assert!(AggregationPolicy::All.combine(3, 3));
+    assert!(!AggregationPolicy::All.combine(2, 3));
+    assert!(AggregationPolicy::Quorum(2).combine(2, 3));
+    assert!(!AggregationPolicy::Quorum(2).combine(1, 3));
+}
+
+#[tokio::test]
+/// Test that mixed healthy and unhealthy endpoints are aggregated by policy.
+async fn it_should_aggregate_mixed_endpoints() {
+    let healthy = MockServer::start(|_, _| MockResponse::status(200)).await;
+    let unhealthy = MockServer::start(|_, _| MockResponse::status(503)).await;
+    let (up, down) = (healthy.uri("/"), unhealthy.uri("/"));
+    let uris = [up.as_str(), up.as_str(), down.as_str()];
+    let client = reqwest::Client::new();
+    let config = WatchConfig::default();
+
+    assert!(check_endpoints(&client, &uris, AggregationPolicy::Any, &config).await.unwrap());
+    assert!(!check_endpoints(&client, &uris, AggregationPolicy::All, &config).await.unwrap());
+    assert!(check_endpoints(&client, &uris, AggregationPolicy::Quorum(2), &config).await.unwrap());
+    assert!(!check_endpoints(&client, &uris, AggregationPolicy::Quorum(3), &config).await.unwrap());
+    assert_eq!(healthy.hits(), 8);
+    assert_eq!(unhealthy.hits(), 4);
+}
+
+static AGGREGATE_TRIPS: AtomicUsize = AtomicUsize::new(0);
+
+fn record_aggregate_trip() {
+    AGGREGATE_TRIPS.fetch_add(1, Ordering::SeqCst);
+}
+
+#[tokio::test]
+/// Test that a multi-endpoint watcher trips when the aggregate health degrades.
+async fn it_should_trip_a_watcher_when_the_aggregate_degrades() {
+    static TRIGGER: Trigger = Trigger::Callback(record_aggregate_trip);
+    let healthy = MockServer::start(|_, _| MockResponse::status(200)).await;
+    let unhealthy = MockServer::start(|_, _| MockResponse::status(503)).await;
+    let uris: Vec<&'static str> = vec![
+        Box::leak(healthy.uri("/").into_boxed_str()),
+        Box::leak(unhealthy.uri("/").into_boxed_str()),
+    ];
+    let handle = EBrake::new(1, 0).watch_service_endpoints(uris, Duration::from_millis(50), &TRIGGER, AggregationPolicy::All).await;
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while AGGREGATE_TRIPS.load(Ordering::SeqCst) == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+    handle.cancel();
+    handle.join().await.unwrap().unwrap();
+    assert!(healthy.hits() > 0);
+}
+
+#[tokio::test]
+/// Test that a shared brake records samples from both the watcher and the application.
+async fn it_should_share_a_watched_brake() {
+    let server = MockServer::start(|_, _| MockResponse::status(200)).await;
+    let uri: &'static str = Box::leak(server.uri("/").into_boxed_str());
+    let ebrake = SharedEBrake::new(EBrake::new(20, 10));
+    let handle = ebrake.clone().watch_service_endpoint(uri, Duration::from_millis(20), &Trigger::Log).await;
+
+    ebrake.add_sample(false);
+    ebrake.add_sample(false);
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while ebrake.lock().current_successes() < 3 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+    handle.cancel();
+    handle.join().await.unwrap().unwrap();
+
+    let ebrake = ebrake.lock();
+    assert_eq!(ebrake.current_failures(), 2);
+    assert_eq!(ebrake.current_successes(), server.hits());
+    assert!(ebrake.failure_rate() > 0.0 && ebrake.failure_rate() < 1.0);
+}
+
+#[tokio::test]
+/// Test that the watch stream yields a check result on every tick without recording samples.
+async fn it_should_yield_results_from_the_watch_stream() {
+    use futures_util::StreamExt;
+
+    let server = MockServer::start(|hit, _| MockResponse::status(if hit % 2 == 0 { 200 } else { 503 })).await;
+    let uri: &'static str = Box::leak(server.uri("/").into_boxed_str());
+    let mut ebrake = EBrake::new(3, 1);
+    let checks = ebrake.watch_stream(uri, Duration::from_millis(20));
+
+    let results: Vec<bool> = tokio::time::timeout(Duration::from_secs(5), checks.take(4).map(Result::unwrap).collect()).await.unwrap();
+    assert_eq!(results, [true, false, true, false]);
+    assert_eq!(server.hits(), 4);
+    assert!(ebrake.is_empty());
+
+    ebrake.extend(results);
+    assert!(ebrake.should_trigger());
+}
+
+#[tokio::test]
+/// Test that dropping the watch stream stops the checks.
+async fn it_should_stop_checking_when_the_stream_is_dropped() {
+    use futures_util::StreamExt;
+
+    let server = MockServer::start(|_, _| MockResponse::status(200)).await;
+    let uri: &'static str = Box::leak(server.uri("/").into_boxed_str());
+    let shared = SharedEBrake::new(EBrake::new(3, 1));
+    let mut checks = Box::pin(shared.watch_stream(uri, Duration::from_millis(20)));
+    assert!(checks.next().await.unwrap().unwrap());
+    drop(checks);
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(server.hits(), 1);
+}
+
+#[tokio::test]
+/// Test that a successful status with a degraded body is counted as a failure.
+async fn it_should_match_the_response_body() {
+    let server = MockServer::start(|hit, _| MockResponse {
+        body: if hit == 0 { r#"{"status":"ok"}"# } else { r#"{"status":"degraded"}"# },
+        ..MockResponse::status(200)
+    })
+    .await;
+    let config = ServiceCheckConfig {
+        body_matcher: Some(BodyMatcher::contains(r#""status":"ok""#)),
+        ..ServiceCheckConfig::default()
+    };
+    let mut ebrake = EBrake::new(2, 0);
+    let healthy = ebrake.check_service_endpoint_with(&server.uri("/"), &config).await;
+    ebrake.add_sample(healthy);
+    let healthy = ebrake.check_service_endpoint_with(&server.uri("/"), &config).await;
+    ebrake.add_sample(healthy);
+    assert_eq!(ebrake.to_vec(), [true, false]);
+    assert!(ebrake.should_trigger());
+
+    // Without a matcher, the status alone decides.
+    assert!(ebrake.check_service_endpoint(&server.uri("/")).await);
+}
+
+#[tokio::test]
+/// Test that the body is not matched when the status is already unacceptable.
+async fn it_should_not_match_the_body_of_a_failed_status() {
+    let server = MockServer::start(|_, _| MockResponse {
+        body: "ok",
+        ..MockResponse::status(503)
+    })
+    .await;
+    let config = ServiceCheckConfig {
+        body_matcher: Some(BodyMatcher::new(|_| true)),
+        ..ServiceCheckConfig::default()
+    };
+    assert!(!EBrake::new(2, 0).check_service_endpoint_with(&server.uri("/"), &config).await);
+}
+
+/// Starts a server that redirects `/health` to a `/login` page that always succeeds.
+async fn redirecting_server() -> MockServer {
+    MockServer::start(|_, request| match request.starts_with("GET /health ") {
+        true => MockResponse::redirect("/login"),
+        false => MockResponse::status(200),
+    })
+    .await
+}
+
+#[tokio::test]
+/// Test that redirects are followed by default, so the check passes against the redirect target.
+async fn it_should_follow_a_redirect_by_default() {
+    let server = redirecting_server().await;
+    let ebrake = EBrake::new(10, 3);
+    assert!(ebrake.check_service_endpoint(&server.uri("/health")).await);
+    assert_eq!(server.hits(), 2);
+    assert!(server.requests()[1].starts_with("GET /login "));
+}
+
+#[tokio::test]
+/// Test that an unfollowed redirect is a failure unless configured otherwise.
+async fn it_should_pin_the_check_to_the_endpoint() {
+    let server = redirecting_server().await;
+    let mut config = ServiceCheckConfig {
+        redirect: RedirectPolicy::None,
+        ..ServiceCheckConfig::default()
+    };
+    let ebrake = EBrake::new(10, 3);
+    assert!(!ebrake.check_service_endpoint_with(&server.uri("/health"), &config).await);
+    assert_eq!(server.hits(), 1);
+
+    config.redirect_is_failure = false;
+    assert!(ebrake.check_service_endpoint_with(&server.uri("/health"), &config).await);
+    assert_eq!(server.hits(), 2);
+}
+
+#[tokio::test]
+/// Test that a check needing more redirects than the limit fails.
+async fn it_should_limit_the_number_of_redirects() {
+    let server = MockServer::start(|_, request| match request.starts_with("GET /login ") {
+        true => MockResponse::status(200),
+        false if request.starts_with("GET /health ") => MockResponse::redirect("/gateway"),
+        false => MockResponse::redirect("/login"),
+    })
+    .await;
+    let ebrake = EBrake::new(10, 3);
+    for (max, expected) in [(0, false), (1, false), (2, true), (3, true)] {
+        let config = ServiceCheckConfig {
+            redirect: RedirectPolicy::Limit(max),
+            ..ServiceCheckConfig::default()
+        };
+        assert_eq!(ebrake.check_service_endpoint_with(&server.uri("/health"), &config).await, expected, "limit {}", max);
+    }
+}
+
+#[tokio::test]
+/// Test that checks still pass with connection reuse disabled.
+async fn it_should_check_without_keep_alive() {
+    let server = MockServer::start(|_, _| MockResponse::status(200)).await;
+    let config = ServiceCheckConfig {
+        keep_alive: false,
+        ..ServiceCheckConfig::default()
+    };
+    let ebrake = EBrake::new(10, 3);
+    assert!(ebrake.check_service_endpoint_with(&server.uri("/"), &config).await);
+    assert!(ebrake.check_service_endpoint_with(&server.uri("/"), &config).await);
+    assert_eq!(server.hits(), 2);
+}
+
+#[tokio::test]
+/// Test that an unresolvable host name is classified as a DNS failure.
+async fn it_should_classify_an_unresolvable_host_as_dns() {
+    let ebrake = EBrake::new(10, 3);
+    let config = ServiceCheckConfig::default();
+    let failure = ebrake.classify_service_endpoint("http://emergency-brake.invalid/health", &config).await;
+    assert_eq!(failure, Some(ErrorKind::Dns));
+    assert!(!ebrake.check_service_endpoint("http://emergency-brake.invalid/health").await);
+}
+
+#[tokio::test]
+/// Test that hyper still reports a DNS failure with a "dns error" message,
+/// which `request_error_kind` matches on. An upgrade that rewords it fails here.
+async fn it_should_report_dns_failures_with_hyper_wording() {
+    let error = reqwest::get("http://emergency-brake.invalid/").await.unwrap_err();
+    assert!(error.is_connect());
+    let mut messages = Vec::new();
+    let mut source: Option<&(dyn std::error::Error + 'static)> = Some(&error);
+    while let Some(cause) = source {
+        messages.push(cause.to_string());
+        source = cause.source();
+    }
+    assert!(messages.iter().any(|message| message.starts_with("dns error")), "{messages:?}");
+}
+
+#[tokio::test]
+/// Test that refused connections, timeouts and statuses are classified apart from DNS failures.
+async fn it_should_classify_check_failures() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let closed = format!("http://{}/", listener.local_addr().unwrap());
+    drop(listener);
+    let server = MockServer::start(|_, request| match request.split(' ').nth(1) {
+        Some("/missing") => MockResponse::status(404),
+        Some("/broken") => MockResponse::status(503),
+        Some("/slow") => MockResponse {
+            delay: Duration::from_secs(5),
+            ..MockResponse::status(200)
+        },
+        _ => MockResponse::status(200),
+    })
+    .await;
+    let config = ServiceCheckConfig {
+        timeout: Duration::from_millis(200),
+        ..ServiceCheckConfig::default()
+    };
+    let ebrake = SharedEBrake::new(EBrake::new(10, 3));
+    for (uri, expected) in [
+        (closed, Some(ErrorKind::Connection)),
+        (server.uri("/slow"), Some(ErrorKind::Timeout)),
+        (server.uri("/missing"), Some(ErrorKind::ClientError)),
+        (server.uri("/broken"), Some(ErrorKind::ServerError)),
+        (server.uri("/"), None),
+    ] {
+        assert_eq!(ebrake.classify_service_endpoint(&uri, &config).await, expected, "{}", uri);
+    }
+}
+
+#[tokio::test]
+/// Test that a classified check feeds the per-category counts.
+async fn it_should_record_a_classified_check() {
+    let mut ebrake = EBrake::new(2, 0);
+    let failure = ebrake.classify_service_endpoint("http://emergency-brake.invalid/", &ServiceCheckConfig::default()).await;
+    ebrake.add_categorized_sample(failure);
+    assert_eq!(ebrake.category_counts()[&ErrorKind::Dns], 1);
+}
+
+/// Returns a configuration whose HTTP client cannot be built.
+fn misconfigured() -> ServiceCheckConfig {
+    ServiceCheckConfig {
+        proxy: Some("not a proxy url".to_string()),
+        ..ServiceCheckConfig::default()
+    }
+}
+
+#[tokio::test]
+/// Test that a client that cannot be built is reported apart from a failed check.
+async fn it_should_report_a_client_build_error() {
+    let server = MockServer::start(|_, _| MockResponse::status(200)).await;
+    let ebrake = SharedEBrake::new(EBrake::new(10, 3));
+    let error = ebrake.try_check_service_endpoint(&server.uri("/"), &misconfigured()).await.unwrap_err();
+    assert!(matches!(error, ServiceCheckError::Client(_)), "{:?}", error);
+    assert!(error.to_string().starts_with("failed to build the HTTP client"));
+    assert!(matches!(EBrake::new(10, 3).try_classify_service_endpoint(&server.uri("/"), &misconfigured()).await, Err(ServiceCheckError::Client(_))));
+    assert!(!ebrake.check_service_endpoint_with(&server.uri("/"), &misconfigured()).await);
+    assert_eq!(server.hits(), 0);
+
+    assert!(ebrake.try_check_service_endpoint(&server.uri("/"), &ServiceCheckConfig::default()).await.unwrap());
+}
+
+#[tokio::test]
+/// Test that a request that cannot be built is reported apart from a failed check.
+async fn it_should_report_an_invalid_request() {
+    let ebrake = EBrake::new(10, 3);
+    let config = ServiceCheckConfig::default();
+    let error = ebrake.try_classify_service_endpoint("not a uri", &config).await.unwrap_err();
+    assert!(matches!(error, ServiceCheckError::Request(_)), "{:?}", error);
+    assert_eq!(ebrake.classify_service_endpoint("not a uri", &config).await, Some(ErrorKind::Other));
+
+    let healthy = ebrake.try_check_service_endpoint("http://emergency-brake.invalid/", &config).await;
+    assert!(!healthy.unwrap());
+}
+
+#[tokio::test]
+/// Test that a watcher with a client that cannot be built does not record failures.
+async fn it_should_not_record_samples_for_a_misconfigured_watcher() {
+    let server = MockServer::start(|_, _| MockResponse::status(200)).await;
+    let uri: &'static str = Box::leak(server.uri("/").into_boxed_str());
+    let ebrake = SharedEBrake::new(EBrake::new(1, 0));
+    let config = WatchConfig {
+        check: misconfigured(),
+        ..WatchConfig::default()
+    };
+    let handle = ebrake.clone().watch_service_endpoint_with(uri, Duration::from_millis(10), &Trigger::Panic, config).await;
+    let stopped = tokio::time::timeout(Duration::from_secs(5), handle.join()).await.unwrap().unwrap();
+    assert!(matches!(stopped, Err(ServiceCheckError::Client(_))), "{:?}", stopped);
+    assert!(ebrake.lock().is_empty());
+    assert_eq!(server.hits(), 0);
+
+    use futures_util::StreamExt;
+    let config = WatchConfig {
+        check: misconfigured(),
+        ..WatchConfig::default()
+    };
+    let checks: Vec<_> = ebrake.watch_stream_with(uri, Duration::from_millis(10), config).collect().await;
+    assert!(matches!(checks[..], [Err(ServiceCheckError::Client(_))]), "{:?}", checks);
+}
+
+#[tokio::test]
+/// Test that a watcher stops without recording failures when its requests cannot be built.
+async fn it_should_stop_a_watcher_with_an_invalid_uri() {
+    let ebrake = SharedEBrake::new(EBrake::new(1, 0));
+    let handle = ebrake.clone().watch_service_endpoint("not a uri", Duration::from_millis(10), &Trigger::Panic).await;
+    let stopped = tokio::time::timeout(Duration::from_secs(5), handle.join()).await.unwrap().unwrap();
+    assert!(matches!(stopped, Err(ServiceCheckError::Request(_))), "{:?}", stopped);
+    assert!(ebrake.lock().is_empty());
+
+    let handle = ebrake.clone().watch_service_endpoints(vec!["http://emergency-brake.invalid/", "not a uri"], Duration::from_millis(10), &Trigger::Panic, AggregationPolicy::Any).await;
+    let stopped = tokio::time::timeout(Duration::from_secs(5), handle.join()).await.unwrap().unwrap();
+    assert!(matches!(stopped, Err(ServiceCheckError::Request(_))), "{:?}", stopped);
+    assert!(ebrake.lock().is_empty());
+
+    use futures_util::StreamExt;
+    let checks: Vec<_> = ebrake.watch_stream("not a uri", Duration::from_millis(10)).collect().await;
+    assert!(matches!(checks[..], [Err(ServiceCheckError::Request(_))]), "{:?}", checks);
+    assert!(ebrake.lock().is_empty());
+}
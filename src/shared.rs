@@ -0,0 +1,86 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// The SharedEBrake is a thread-safe handle to an EBrake.
+/// Clones share the same sample window, so a clone can be handed to each worker
+/// thread and samples recorded through shared references.
+/// ```
+/// use emergency_brake::*;
+/// let ebrake = SharedEBrake::new(EBrake::new(10, 3));
+/// let worker = ebrake.clone();
+/// std::thread::spawn(move || worker.add_sample(true)).join().unwrap();
+/// assert_eq!(ebrake.lock().current_successes(), 1);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SharedEBrake {
+    inner: Arc<Mutex<EBrake>>,
+}
+
+impl SharedEBrake {
+    /// Wraps the given EBrake for sharing between threads.
+    pub fn new(ebrake: EBrake) -> Self {
+        SharedEBrake {
+            inner: Arc::new(Mutex::new(ebrake)),
+        }
+    }
+
+    /// Locks the shared EBrake for direct access.
+    /// A panic trigger fired while the lock was held does not leave the
+    /// EBrake unusable, so a poisoned lock is recovered.
+    pub fn lock(&self) -> MutexGuard<'_, EBrake> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Insert a sample into the shared emergency brake.
+    pub fn add_sample(&self, sample: bool) {
+        self.lock().add_sample(sample);
+    }
+
+    /// Returns true if the shared emergency brake should be triggered.
+    pub fn should_trigger(&self) -> bool {
+        self.lock().should_trigger()
+    }
+
+    /// Returns `TripOutcome::NotTripped` if the shared emergency brake has not been triggered.
+    /// If it has been triggered, the supplied trigger action will be executed.
+    pub fn trigger(&self, trigger: &Trigger) -> TripOutcome {
+        self.lock().trigger(trigger)
+    }
+
+    /// Insert a sample and check if the shared emergency brake should be triggered.
+    pub fn trigger_on_sample(&self, sample: bool, trigger: &Trigger) -> TripOutcome {
+        self.lock().trigger_on_sample(sample, trigger)
+    }
+
+    /// Similar to trigger, but takes the trigger action by value.
+    pub fn trigger_with(&self, trigger: Trigger) -> TripOutcome {
+        self.trigger(&trigger)
+    }
+
+    /// Similar to trigger_on_sample, but takes the trigger action by value.
+    pub fn trigger_on_sample_with(&self, sample: bool, trigger: Trigger) -> TripOutcome {
+        self.trigger_on_sample(sample, &trigger)
+    }
+
+    /// Clear the shared sample window.
+    pub fn reset(&self) {
+        self.lock().reset();
+    }
+}
+
+impl From<EBrake> for SharedEBrake {
+    fn from(ebrake: EBrake) -> Self {
+        SharedEBrake::new(ebrake)
+    }
+}
+
+
+/// Test module for the SharedEBrake.
+#[cfg(test)]
+mod test;
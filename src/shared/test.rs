@@ -0,0 +1,52 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+use std::thread;
+
+#[test]
+/// Test that the shared brake can be sent to and shared between threads.
+fn it_should_be_send_and_sync() {
+    fn assert_send_sync<T: Clone + Send + Sync>() {}
+    assert_send_sync::<SharedEBrake>();
+}
+
+#[test]
+/// Test that samples recorded concurrently from several threads are all counted.
+fn it_should_record_samples_from_many_threads() {
+    let ebrake = SharedEBrake::new(EBrake::new(1_000, 999));
+    let workers: Vec<_> = (0..8)
+        .map(|worker| {
+            let ebrake = ebrake.clone();
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    ebrake.add_sample(worker % 2 == 0);
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    let inner = ebrake.lock();
+    assert_eq!(inner.len(), 800);
+    assert_eq!(inner.current_successes(), 400);
+    assert_eq!(inner.current_failures(), 400);
+}
+
+#[test]
+/// Test that the shared brake remains usable after a panic trigger fires.
+fn it_should_recover_after_a_panic_trigger() {
+    let ebrake = SharedEBrake::new(EBrake::new(2, 0));
+    ebrake.add_sample(false);
+    let worker = ebrake.clone();
+    let result = thread::spawn(move || worker.trigger_on_sample(false, &Trigger::Panic)).join();
+    assert!(result.is_err());
+
+    assert!(ebrake.should_trigger());
+    ebrake.reset();
+    assert!(!ebrake.should_trigger());
+}
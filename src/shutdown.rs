@@ -0,0 +1,45 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+use tokio::sync::oneshot;
+
+impl EBrake {
+    /// Returns `TripOutcome::NotTripped` if the emergency brake has not been triggered.
+    /// If the emergency brake has been triggered, the sender is taken and fired,
+    /// so that a task awaiting the receiver can drain in-flight work and exit
+    /// cleanly. Once the sender has been taken, later calls still report the
+    /// trip but have nothing left to signal.
+    /// ```
+    /// use emergency_brake::*;
+    /// use tokio::sync::oneshot;
+    /// let (tx, mut rx) = oneshot::channel();
+    /// let mut shutdown = Some(tx);
+    /// let mut ebrake = EBrake::new(2, 0);
+    /// ebrake.add_samples(&[true, false]);
+    /// assert_eq!(ebrake.trigger_shutdown(&mut shutdown), TripOutcome::Handled);
+    /// assert!(rx.try_recv().is_ok());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "shutdown")))]
+    pub fn trigger_shutdown(&self, shutdown: &mut Option<oneshot::Sender<()>>) -> TripOutcome {
+        match self.should_fire() {
+            true => {
+                self.log_trip("Emergency brake shutdown triggered!");
+                self.notify_trip();
+                if let Some(sender) = shutdown.take() {
+                    // The receiver may already be gone if the service is shutting down.
+                    let _ = sender.send(());
+                }
+                TripOutcome::Handled
+            },
+            false => TripOutcome::NotTripped,
+        }
+    }
+}
+
+/// Test module for the shutdown trigger.
+#[cfg(test)]
+mod test;
@@ -0,0 +1,49 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+use tokio::sync::oneshot::error::TryRecvError;
+
+#[test]
+/// Test that the receiver resolves exactly when the threshold is crossed.
+fn it_should_signal_shutdown_when_the_threshold_is_crossed() {
+    let (tx, mut rx) = oneshot::channel();
+    let mut shutdown = Some(tx);
+    let mut ebrake = EBrake::new(3, 1);
+    for sample in [false, true] {
+        ebrake.add_sample(sample);
+        assert_eq!(ebrake.trigger_shutdown(&mut shutdown), TripOutcome::NotTripped);
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    }
+
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.trigger_shutdown(&mut shutdown), TripOutcome::Handled);
+    assert!(shutdown.is_none());
+    assert_eq!(rx.try_recv(), Ok(()));
+}
+
+#[test]
+/// Test that a spent sender still reports the trip.
+fn it_should_report_the_trip_after_the_sender_is_spent() {
+    let (tx, rx) = oneshot::channel();
+    drop(rx);
+    let mut shutdown = Some(tx);
+    let mut ebrake = EBrake::new(1, 0);
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.trigger_shutdown(&mut shutdown), TripOutcome::Handled);
+    assert_eq!(ebrake.trigger_shutdown(&mut shutdown), TripOutcome::Handled);
+}
+
+#[tokio::test]
+/// Test that a task awaiting the receiver wakes up when the brake trips.
+async fn it_should_wake_the_awaiting_task() {
+    let (tx, rx) = oneshot::channel();
+    let waiter = tokio::spawn(rx);
+    let mut shutdown = Some(tx);
+    let mut ebrake = EBrake::new(2, 0);
+    ebrake.add_samples(&[false, false]);
+    ebrake.trigger_shutdown(&mut shutdown);
+    assert_eq!(waiter.await.unwrap(), Ok(()));
+}
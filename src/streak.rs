@@ -0,0 +1,39 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+impl EBrake {
+    /// Returns the number of consecutive failures ending with the newest sample.
+    /// Any success resets the streak. The streak is not bounded by the window
+    /// size, so it keeps counting as old samples are evicted.
+    pub fn current_streak(&self) -> usize {
+        self.failure_streak
+    }
+
+    /// Returns true if the current failure streak has reached `max_streak`,
+    /// regardless of how full the window is.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(25, 10);
+    /// ebrake.add_samples(&[false, false, false]);
+    /// assert!(ebrake.should_trigger_streak(3));
+    /// assert!(!ebrake.should_trigger());
+    /// ```
+    pub fn should_trigger_streak(&self, max_streak: usize) -> bool {
+        self.failure_streak >= max_streak
+    }
+
+    /// Makes the emergency brake also trip as soon as `max_streak` consecutive
+    /// failures are recorded, in addition to the windowed threshold.
+    /// A limit of zero disables the streak condition.
+    pub fn set_max_streak(&mut self, max_streak: usize) {
+        self.max_streak = (max_streak > 0).then_some(max_streak);
+    }
+}
+
+/// Test module for the failure streak detector.
+#[cfg(test)]
+mod test;
@@ -0,0 +1,80 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+#[test]
+/// Test that a run of failures is counted and reset by a success.
+fn it_should_count_consecutive_failures() {
+    let mut ebrake = EBrake::new(10, 5);
+    ebrake.add_samples(&[false, false, true, false]);
+    assert_eq!(ebrake.current_streak(), 1);
+
+    ebrake.add_samples(&[false, false]);
+    assert_eq!(ebrake.current_streak(), 3);
+}
+
+#[test]
+/// Test that an interleaved success prevents a streak trip.
+fn it_should_not_trip_on_an_interrupted_streak() {
+    let mut ebrake = EBrake::new(10, 9);
+    ebrake.set_max_streak(3);
+    ebrake.add_samples(&[false, false, true, false, false]);
+    assert!(!ebrake.should_trigger_streak(3));
+    assert!(!ebrake.should_trigger());
+}
+
+#[test]
+/// Test that a pure failure run trips the brake before the window is full.
+fn it_should_trip_on_a_pure_failure_run() {
+    let mut ebrake = EBrake::new(10, 9);
+    ebrake.set_max_streak(3);
+    ebrake.add_samples(&[true, false, false]);
+    assert!(!ebrake.should_trigger());
+
+    ebrake.add_sample(false);
+    assert!(ebrake.should_trigger_streak(3));
+    assert!(ebrake.should_trigger());
+}
+
+#[test]
+/// Test that the streak outlives the samples evicted from a small window.
+fn it_should_count_a_streak_longer_than_the_window() {
+    let mut ebrake = EBrake::new(2, 1);
+    for _ in 0..4 {
+        ebrake.add_sample(false);
+    }
+    assert_eq!(ebrake.len(), 2);
+    assert_eq!(ebrake.current_streak(), 4);
+}
+
+#[test]
+/// Test that a batch longer than the window still counts its whole streak.
+fn it_should_count_the_streak_of_a_long_batch() {
+    let mut ebrake = EBrake::new(2, 1);
+    ebrake.add_sample(false);
+    ebrake.add_samples(&[false, false, false]);
+    assert_eq!(ebrake.current_streak(), 4);
+
+    ebrake.add_samples(&[false, true, false, false]);
+    assert_eq!(ebrake.current_streak(), 2);
+}
+
+#[test]
+/// Test that a streak limit of zero disables the streak condition.
+fn it_should_disable_the_streak_with_a_zero_limit() {
+    let mut ebrake = EBrake::new(10, 9);
+    ebrake.set_max_streak(0);
+    assert!(!ebrake.should_trigger());
+}
+
+#[test]
+/// Test that reset clears the streak.
+fn it_should_reset_the_streak() {
+    let mut ebrake = EBrake::new(10, 5);
+    ebrake.add_samples(&[false, false]);
+    ebrake.reset();
+    assert_eq!(ebrake.current_streak(), 0);
+}
@@ -0,0 +1,49 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+impl EBrake {
+    /// Makes the emergency brake also trip when the fraction of successes in
+    /// the window falls below the given rate, for a heartbeat or probe that
+    /// must stay healthy. Like the failure threshold, this only applies once
+    /// the minimum number of samples has been recorded. The rate must be within
+    /// `(0.0, 1.0]`.
+    /// ```
+    /// use emergency_brake::*;
+    /// let mut ebrake = EBrake::new(10, 5);
+    /// ebrake.set_min_success_rate(0.9).unwrap();
+    /// ebrake.add_samples(&[true, true, false, true, true, true, true, false, true, true]);
+    /// assert_eq!(ebrake.current_failures(), 2);
+    /// assert!(ebrake.should_trigger());
+    /// ```
+    pub fn set_min_success_rate(&mut self, rate: f64) -> Result<(), EBrakeError> {
+        if !(rate > 0.0 && rate <= 1.0) {
+            return Err(EBrakeError::InvalidSuccessRate);
+        }
+
+        self.min_success_rate = Some(rate);
+        Ok(())
+    }
+
+    /// Returns the minimum success rate, if one is set.
+    pub fn min_success_rate(&self) -> Option<f64> {
+        self.min_success_rate
+    }
+
+    /// Returns the fraction of samples in the current window that are successes.
+    /// An empty window has a success rate of `0.0`.
+    pub fn success_rate(&self) -> f64 {
+        if self.data.is_empty() {
+            return 0.0;
+        }
+
+        self.successes as f64 / self.data.len() as f64
+    }
+}
+
+/// Test module for the minimum success rate.
+#[cfg(test)]
+mod test;
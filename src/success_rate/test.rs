@@ -0,0 +1,45 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+#[test]
+/// Test that a drop in successes trips the brake while failures stay within tolerance.
+fn it_should_trip_when_successes_drop() {
+    let mut ebrake = EBrake::new(10, 5);
+    ebrake.set_min_success_rate(0.9).unwrap();
+    ebrake.extend([true; 9]);
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.success_rate(), 0.9);
+    assert!(!ebrake.should_trigger());
+
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.current_failures(), 2);
+    assert!(ebrake.current_failures() <= 5);
+    assert!(ebrake.should_trigger());
+}
+
+#[test]
+/// Test that the success rate only applies once the window is full.
+fn it_should_wait_for_a_full_window() {
+    let mut ebrake = EBrake::new(4, 3);
+    ebrake.set_min_success_rate(0.8).unwrap();
+    ebrake.extend([false, true, true]);
+    assert!(!ebrake.should_trigger());
+    assert!(ebrake.would_trigger(true));
+
+    ebrake.add_sample(true);
+    assert!(ebrake.should_trigger());
+}
+
+#[test]
+/// Test that an invalid success rate is rejected.
+fn it_should_reject_an_invalid_success_rate() {
+    let mut ebrake = EBrake::new(4, 1);
+    for rate in [0.0, -0.1, 1.1, f64::NAN] {
+        assert_eq!(ebrake.set_min_success_rate(rate), Err(EBrakeError::InvalidSuccessRate));
+    }
+    assert_eq!(ebrake.min_success_rate(), None);
+}
@@ -4,6 +4,72 @@
  */
 
 use super::*;
+use std::collections::HashMap;
+use std::env;
+use std::process::{Command, ExitStatus};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+
+/// Environment variable marking a test binary spawned by `run_in_subprocess`.
+const SUBPROCESS_ENV: &str = "EBRAKE_TEST_SUBPROCESS";
+
+/// Re-runs the named test in a child process and returns its exit status.
+/// The child sees `SUBPROCESS_ENV` set, which lets a test perform a process-level
+/// trigger action without terminating the test harness itself.
+fn run_in_subprocess(test_name: &str) -> ExitStatus {
+    Command::new(env::current_exe().unwrap())
+        .args(["--exact", test_name, "--test-threads=1"])
+        .env(SUBPROCESS_ENV, "1")
+        .status()
+        .unwrap()
+}
+
+fn in_subprocess() -> bool {
+    env::var_os(SUBPROCESS_ENV).is_some()
+}
+
+/// A tracing event recorded by `capture_events`.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct CapturedEvent {
+    pub(crate) level: String,
+    pub(crate) fields: HashMap<String, String>,
+}
+
+/// A tracing layer that records every event it observes.
+struct CaptureLayer {
+    events: Arc<Mutex<Vec<CapturedEvent>>>,
+}
+
+impl<S: tracing::Subscriber> Layer<S> for CaptureLayer {
+    fn on_event(&self, event: &tracing::Event<'_>, _: Context<'_, S>) {
+        struct Visitor<'a>(&'a mut HashMap<String, String>);
+        impl Visit for Visitor<'_> {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                self.0.insert(field.name().to_string(), format!("{:?}", value).trim_matches('"').to_string());
+            }
+        }
+
+        let mut captured = CapturedEvent {
+            level: event.metadata().level().to_string(),
+            ..CapturedEvent::default()
+        };
+        event.record(&mut Visitor(&mut captured.fields));
+        self.events.lock().unwrap().push(captured);
+    }
+}
+
+/// Runs the closure with a subscriber that captures all tracing events on this thread.
+pub(crate) fn capture_events(f: impl FnOnce()) -> Vec<CapturedEvent> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let subscriber = tracing_subscriber::registry().with(CaptureLayer {
+        events: Arc::clone(&events),
+    });
+    tracing::subscriber::with_default(subscriber, f);
+    let captured = events.lock().unwrap().clone();
+    captured
+}
 
 #[test]
 /// Test that the emergency brake can be created with default values.
@@ -16,7 +82,1189 @@ fn it_should_create_with_defaults() {
     assert_eq!(ebrake.tolerance, 0);
 }
 
+#[test]
+/// Test that a zero-size window never stores samples or triggers.
+fn it_should_never_store_samples_in_a_zero_window() {
+    let mut ebrake = EBrake::default();
+    ebrake.set_max_streak(1);
+    for _ in 0..10 {
+        ebrake.add_sample(false);
+    }
+    ebrake.add_weighted_sample(false, 5);
+    ebrake.add_samples(&[false, false]);
+    assert!(ebrake.is_empty());
+    assert_eq!(ebrake.current_failures(), 0);
+    assert_eq!(ebrake.total_samples(), 13);
+    assert!(!ebrake.should_trigger());
+    assert!(!ebrake.would_trigger(false));
+    assert_eq!(ebrake.trigger(&Trigger::Panic), TripOutcome::NotTripped);
+}
+
+#[test]
+/// Test that resizing to a zero-size window empties it and stops it triggering.
+fn it_should_empty_the_window_when_resized_to_zero() {
+    let mut ebrake = EBrake::new(3, 0);
+    ebrake.set_max_streak(2);
+    ebrake.extend([false, false, false]);
+    assert!(ebrake.should_trigger());
+
+    ebrake.resize(0);
+    assert!(ebrake.is_empty());
+    assert!(!ebrake.should_trigger());
+    ebrake.add_sample(false);
+    assert!(ebrake.is_empty());
+}
+
 #[test]
 /// Test that the emergency brake returns false when not triggered.
 fn it_should_return_false_when_not_triggered() {
-}
\ No newline at end of file
+}
+
+#[test]
+/// Test that resetting the emergency brake clears the window but keeps its configuration.
+fn it_should_reset_the_sample_window() {
+    let mut ebrake = EBrake::new(10, 3);
+    for _ in 0..10 {
+        ebrake.add_sample(false);
+    }
+    assert!(ebrake.should_trigger());
+
+    ebrake.reset();
+    assert_eq!(ebrake.data.len(), 0);
+    assert_eq!(ebrake.failures, 0);
+    assert_eq!(ebrake.successes, 0);
+    assert_eq!(ebrake.samples, 10);
+    assert_eq!(ebrake.tolerance, 3);
+    assert!(!ebrake.should_trigger());
+}
+
+#[test]
+/// Test that an empty window has a failure rate of zero.
+fn it_should_report_zero_failure_rate_when_empty() {
+    let ebrake = EBrake::new(10, 3);
+    assert_eq!(ebrake.failure_rate(), 0.0);
+}
+
+#[test]
+/// Test that a half-failing window reports a failure rate of one half.
+fn it_should_report_half_failure_rate() {
+    let mut ebrake = EBrake::new(10, 3);
+    for i in 0..10 {
+        ebrake.add_sample(i % 2 == 0);
+    }
+    assert_eq!(ebrake.failure_rate(), 0.5);
+}
+
+#[test]
+/// Test that a fully-failing window reports a failure rate of one.
+fn it_should_report_full_failure_rate() {
+    let mut ebrake = EBrake::new(10, 3);
+    for _ in 0..10 {
+        ebrake.add_sample(false);
+    }
+    assert_eq!(ebrake.failure_rate(), 1.0);
+}
+
+#[test]
+/// Test that the failure and success accessors track the window as it rolls over.
+fn it_should_track_current_counts_across_rollover() {
+    let mut ebrake = EBrake::new(4, 3);
+    ebrake.add_sample(true);
+    ebrake.add_sample(false);
+    ebrake.add_sample(true);
+    assert_eq!(ebrake.current_failures(), 1);
+    assert_eq!(ebrake.current_successes(), 2);
+
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.current_failures(), 2);
+    assert_eq!(ebrake.current_successes(), 2);
+
+    // Evicts the oldest success.
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.current_failures(), 3);
+    assert_eq!(ebrake.current_successes(), 1);
+
+    // Evicts the oldest failure.
+    ebrake.add_sample(true);
+    assert_eq!(ebrake.current_failures(), 2);
+    assert_eq!(ebrake.current_successes(), 2);
+}
+
+#[test]
+/// Test that the window reports full exactly when the last sample slot is filled.
+fn it_should_report_full_when_window_is_filled() {
+    let mut ebrake = EBrake::new(5, 3);
+    assert!(ebrake.is_empty());
+    assert!(!ebrake.is_full());
+
+    for i in 1..5 {
+        ebrake.add_sample(true);
+        assert_eq!(ebrake.len(), i);
+        assert!(!ebrake.is_empty());
+        assert!(!ebrake.is_full());
+    }
+
+    ebrake.add_sample(true);
+    assert_eq!(ebrake.len(), 5);
+    assert!(ebrake.is_full());
+
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.len(), 5);
+    assert!(ebrake.is_full());
+}
+
+#[test]
+/// Test that the emergency brake does not trigger before the window is full.
+/// Regression test: the warm-up gate used to compare against the tolerance.
+fn it_should_not_trigger_before_window_is_full() {
+    let mut ebrake = EBrake::new(25, 3);
+    for _ in 0..4 {
+        ebrake.add_sample(false);
+    }
+    assert!(!ebrake.should_trigger());
+
+    for _ in 4..25 {
+        ebrake.add_sample(true);
+    }
+    assert!(ebrake.should_trigger());
+}
+
+#[test]
+/// Test that a zero-sized window is rejected.
+fn it_should_reject_a_zero_window() {
+    assert_eq!(EBrake::try_new(0, 0).unwrap_err(), EBrakeError::ZeroWindow);
+}
+
+#[test]
+/// Test that a tolerance that can never be exceeded is rejected.
+fn it_should_reject_a_tolerance_that_is_too_large() {
+    assert_eq!(EBrake::try_new(5, 5).unwrap_err(), EBrakeError::ToleranceTooLarge);
+    assert_eq!(EBrake::try_new(5, 6).unwrap_err(), EBrakeError::ToleranceTooLarge);
+}
+
+#[test]
+/// Test that a valid configuration is accepted.
+fn it_should_accept_a_valid_configuration() {
+    let ebrake = EBrake::try_new(5, 4).unwrap();
+    assert_eq!(ebrake.samples, 5);
+    assert_eq!(ebrake.tolerance, 4);
+}
+
+static CALLBACK_FIRED: AtomicUsize = AtomicUsize::new(0);
+
+fn record_callback() {
+    CALLBACK_FIRED.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test]
+/// Test that a callback trigger runs exactly once when the threshold is crossed.
+fn it_should_invoke_the_callback_trigger() {
+    static TRIGGER: Trigger = Trigger::Callback(record_callback);
+    let mut ebrake = EBrake::new(5, 1);
+    for _ in 0..4 {
+        assert!(!ebrake.trigger_on_sample(false, &TRIGGER).is_tripped());
+    }
+    assert_eq!(CALLBACK_FIRED.load(Ordering::SeqCst), 0);
+
+    assert!(ebrake.trigger_on_sample(false, &TRIGGER).is_tripped());
+    assert_eq!(CALLBACK_FIRED.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+/// Test that an exit trigger terminates the process with the configured exit code.
+fn it_should_exit_with_the_configured_code() {
+    if in_subprocess() {
+        static TRIGGER: Trigger = Trigger::Exit(42);
+        let mut ebrake = EBrake::new(2, 1);
+        ebrake.add_sample(false);
+        ebrake.trigger_on_sample(false, &TRIGGER);
+        unreachable!("the exit trigger should have terminated the process");
+    }
+
+    let status = run_in_subprocess("test::it_should_exit_with_the_configured_code");
+    assert_eq!(status.code(), Some(42));
+}
+
+#[test]
+/// Test that a log trigger reports the trip without terminating the process.
+fn it_should_survive_a_log_trigger() {
+    let mut ebrake = EBrake::new(3, 1);
+    assert!(!ebrake.trigger_on_sample(false, &Trigger::Log).is_tripped());
+    assert!(!ebrake.trigger_on_sample(false, &Trigger::Log).is_tripped());
+    assert!(ebrake.trigger_on_sample(false, &Trigger::Log).is_tripped());
+    assert!(ebrake.trigger(&Trigger::Log).is_tripped());
+}
+
+#[test]
+/// Test that a return trigger reports the trip to the caller without diverging.
+fn it_should_return_from_a_return_trigger() {
+    let result = std::panic::catch_unwind(|| {
+        let mut ebrake = EBrake::new(2, 0);
+        ebrake.add_sample(false);
+        let outcome = ebrake.trigger_on_sample(false, &Trigger::Return);
+        let mut fixed = EBrakeN::<1>::new(0);
+        fixed.add_sample(false);
+        let atomic = AtomicEBrake::new(1, 0);
+        atomic.add_sample(false);
+        [outcome, fixed.trigger(&Trigger::Return), atomic.trigger(&Trigger::Return)]
+    });
+    assert_eq!(result.unwrap(), [TripOutcome::Handled; 3]);
+}
+
+#[test]
+/// Test that a trigger chosen at runtime can be passed by a local reference.
+fn it_should_accept_a_non_static_trigger() {
+    let configured = String::from("log");
+    let trigger = match configured.as_str() {
+        "abort" => Trigger::Abort,
+        _ => Trigger::Log,
+    };
+    let mut ebrake = EBrake::new(2, 0);
+    ebrake.add_sample(false);
+    assert!(ebrake.trigger_on_sample(false, &trigger).is_tripped());
+}
+
+#[test]
+/// Test that a rate-based brake triggers exactly at the configured rate.
+fn it_should_trigger_at_the_failure_rate_boundary() {
+    let mut ebrake = EBrake::new_with_rate(10, 0.3).unwrap();
+    for i in 0..10 {
+        ebrake.add_sample(i < 8);
+    }
+    assert_eq!(ebrake.failures, 2);
+    assert!(!ebrake.should_trigger());
+
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.failures, 3);
+    assert!(ebrake.should_trigger());
+}
+
+#[test]
+/// Test that a rate-based brake waits for a full window.
+fn it_should_not_trigger_on_rate_before_window_is_full() {
+    let mut ebrake = EBrake::new_with_rate(10, 0.2).unwrap();
+    for _ in 0..9 {
+        ebrake.add_sample(false);
+    }
+    assert!(!ebrake.should_trigger());
+}
+
+#[test]
+/// Test that a rate of one only triggers when every sample fails.
+fn it_should_trigger_on_a_full_rate_only_when_all_fail() {
+    let mut ebrake = EBrake::new_with_rate(4, 1.0).unwrap();
+    ebrake.add_sample(true);
+    for _ in 0..3 {
+        ebrake.add_sample(false);
+    }
+    assert!(!ebrake.should_trigger());
+
+    ebrake.add_sample(false);
+    assert!(ebrake.should_trigger());
+}
+
+#[test]
+/// Test that failure rates outside of (0.0, 1.0] are rejected.
+fn it_should_reject_an_invalid_failure_rate() {
+    assert_eq!(EBrake::new_with_rate(10, 0.0).unwrap_err(), EBrakeError::InvalidFailureRate);
+    assert_eq!(EBrake::new_with_rate(10, 1.01).unwrap_err(), EBrakeError::InvalidFailureRate);
+    assert_eq!(EBrake::new_with_rate(10, f64::NAN).unwrap_err(), EBrakeError::InvalidFailureRate);
+    assert_eq!(EBrake::new_with_rate(0, 0.5).unwrap_err(), EBrakeError::ZeroWindow);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+/// Test that a partially filled brake survives a round trip through JSON.
+fn it_should_round_trip_through_json() {
+    let mut ebrake = EBrake::new(10, 3);
+    for sample in [true, false, true, true, false] {
+        ebrake.add_sample(sample);
+    }
+
+    let json = serde_json::to_string(&ebrake).unwrap();
+    let restored: EBrake = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.data.iter().collect::<Vec<_>>(), vec![true, false, true, true, false]);
+    assert_eq!(restored.failures, 2);
+    assert_eq!(restored.successes, 3);
+    assert_eq!(restored.samples, 10);
+    assert_eq!(restored.tolerance, 3);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+/// Test that a restored brake continues evicting samples correctly.
+fn it_should_keep_evicting_after_deserializing() {
+    let mut ebrake = EBrake::new(3, 1);
+    for sample in [false, false, true] {
+        ebrake.add_sample(sample);
+    }
+
+    let json = serde_json::to_string(&ebrake).unwrap();
+    let mut restored: EBrake = serde_json::from_str(&json).unwrap();
+    assert!(restored.should_trigger());
+    restored.add_sample(true);
+    assert_eq!(restored.current_failures(), 1);
+    assert!(!restored.should_trigger());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+/// Test that the log level is serialized by name and may be omitted.
+fn it_should_serialize_the_log_level_by_name() {
+    let mut ebrake = EBrake::new(2, 0);
+    ebrake.set_log_level(tracing::Level::INFO);
+    let mut json = serde_json::to_value(&ebrake).unwrap();
+    assert_eq!(json["log_level"], "INFO");
+    assert_eq!(serde_json::from_value::<EBrake>(json.clone()).unwrap().log_level(), tracing::Level::INFO);
+
+    json.as_object_mut().unwrap().remove("log_level");
+    json.as_object_mut().unwrap().remove("latched");
+    let restored: EBrake = serde_json::from_value(json).unwrap();
+    assert_eq!(restored.log_level(), EBrake::new(2, 0).log_level());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+/// Test that JSON whose counters disagree with its window is rejected.
+fn it_should_reject_a_corrupt_window_in_json() {
+    let mut ebrake = EBrake::new(3, 1);
+    ebrake.extend([false, true]);
+    let json = serde_json::to_value(&ebrake).unwrap();
+
+    for (field, value) in [("failures", 5), ("samples", 1), ("successes", 0)] {
+        let mut corrupt = json.clone();
+        corrupt[field] = value.into();
+        let error = serde_json::from_value::<EBrake>(corrupt).unwrap_err();
+        assert!(error.to_string().contains("window"), "{}: {}", field, error);
+    }
+    assert_eq!(serde_json::from_value::<EBrake>(json).unwrap(), ebrake);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+/// Test that triggers round trip through JSON.
+fn it_should_round_trip_triggers_through_json() {
+    for trigger in [Trigger::Abort, Trigger::Panic, Trigger::Exit(3), Trigger::Return, Trigger::Log] {
+        let json = serde_json::to_string(&trigger).unwrap();
+        assert_eq!(serde_json::from_str::<Trigger>(&json).unwrap(), trigger);
+    }
+}
+
+#[test]
+/// Test that a batch longer than the window leaves only its last samples.
+fn it_should_add_a_batch_larger_than_the_window() {
+    let mut ebrake = EBrake::new(4, 1);
+    ebrake.add_sample(false);
+    ebrake.add_samples(&[false, false, false, true, false, true, true]);
+    assert_eq!(ebrake.data.iter().collect::<Vec<_>>(), vec![true, false, true, true]);
+    assert_eq!(ebrake.failures, 1);
+    assert_eq!(ebrake.successes, 3);
+    assert!(!ebrake.should_trigger());
+}
+
+#[test]
+/// Test that a batch shorter than the window keeps the existing samples.
+fn it_should_add_a_batch_smaller_than_the_window() {
+    let mut ebrake = EBrake::new(4, 1);
+    ebrake.add_sample(true);
+    ebrake.add_sample(true);
+    ebrake.add_samples(&[false, false, false]);
+    assert_eq!(ebrake.data.iter().collect::<Vec<_>>(), vec![true, false, false, false]);
+    assert_eq!(ebrake.failures, 3);
+    assert!(ebrake.should_trigger());
+}
+
+#[test]
+/// Test that extending a brake applies the circular buffer eviction.
+fn it_should_extend_with_eviction() {
+    let mut ebrake = EBrake::new(3, 1);
+    ebrake.extend([false, false, false, true, true]);
+    assert_eq!(ebrake.data.iter().collect::<Vec<_>>(), vec![false, true, true]);
+    assert_eq!(ebrake.failures, 1);
+    assert_eq!(ebrake.successes, 2);
+}
+
+#[test]
+/// Test that collecting samples sizes the window to fit them all.
+fn it_should_collect_into_a_window_of_matching_size() {
+    let ebrake: EBrake = [true, false, true, true].into_iter().collect();
+    assert_eq!(ebrake.samples, 4);
+    assert_eq!(ebrake.tolerance, 0);
+    assert_eq!(ebrake.failures, 1);
+    assert_eq!(ebrake.successes, 3);
+    assert!(ebrake.should_trigger());
+}
+
+#[test]
+/// Test that a snapshot matches the brake's state after a mixed sample sequence.
+fn it_should_snapshot_the_window() {
+    let mut ebrake = EBrake::new(4, 1);
+    ebrake.extend([true, false, true, false, false]);
+    assert_eq!(
+        ebrake.snapshot(),
+        BrakeSnapshot {
+            window_size: 4,
+            filled: 4,
+            failures: 3,
+            successes: 1,
+            failure_rate: 0.75,
+            tripped: true,
+            total_samples: 5,
+            total_trips: 1,
+        }
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+/// Test that a snapshot can be emitted as a JSON log line.
+fn it_should_serialize_a_snapshot() {
+    let mut ebrake = EBrake::new(4, 1);
+    ebrake.add_sample(false);
+    let json = serde_json::to_value(ebrake.snapshot()).unwrap();
+    assert_eq!(json["filled"], 1);
+    assert_eq!(json["failure_rate"], 1.0);
+    assert_eq!(json["tripped"], false);
+}
+
+#[test]
+/// Test that every sample emits a trace event with the running counts.
+fn it_should_trace_each_sample() {
+    let mut ebrake = EBrake::new(3, 1);
+    let events = capture_events(|| ebrake.extend([true, false, false]));
+    let samples: Vec<_> = events
+        .iter()
+        .filter(|event| event.fields["message"] == "Emergency brake sample recorded")
+        .collect();
+
+    assert_eq!(samples.len(), 3);
+    assert!(samples.iter().all(|event| event.level == "TRACE"));
+    assert_eq!(samples[0].fields["sample"], "true");
+    assert_eq!(samples[0].fields["failures"], "0");
+    assert_eq!(samples[2].fields["sample"], "false");
+    assert_eq!(samples[2].fields["failures"], "2");
+    assert_eq!(samples[2].fields["successes"], "1");
+}
+
+#[test]
+/// Test that heavy failures trip the brake sooner than light ones.
+fn it_should_trip_sooner_on_heavy_failures() {
+    let mut light = EBrake::new(5, 4);
+    let mut heavy = EBrake::new(5, 4);
+    for _ in 0..3 {
+        light.add_sample(true);
+        heavy.add_sample(true);
+    }
+    light.add_weighted_sample(false, 1);
+    light.add_weighted_sample(false, 1);
+    heavy.add_weighted_sample(false, 1);
+    heavy.add_weighted_sample(false, 4);
+
+    assert_eq!(light.failure_weight(), 2);
+    assert_eq!(heavy.failure_weight(), 5);
+    assert!(!light.should_trigger());
+    assert!(heavy.should_trigger());
+}
+
+#[test]
+/// Test that evicting a weighted failure subtracts its weight.
+fn it_should_subtract_evicted_weights() {
+    let mut ebrake = EBrake::new(3, 4);
+    ebrake.add_sample(false);
+    ebrake.add_weighted_sample(false, 10);
+    ebrake.add_sample(true);
+    assert_eq!(ebrake.failure_weight(), 11);
+
+    ebrake.add_sample(true);
+    assert_eq!(ebrake.failure_weight(), 10);
+    assert!(ebrake.should_trigger());
+
+    ebrake.add_sample(true);
+    assert_eq!(ebrake.failure_weight(), 0);
+    assert_eq!(ebrake.failures, 0);
+    assert!(!ebrake.should_trigger());
+}
+
+#[test]
+/// Test that shrinking a window evicts its oldest samples.
+fn it_should_evict_oldest_samples_when_shrinking() {
+    let mut ebrake = EBrake::new(6, 1);
+    ebrake.extend([false, false, true, false, true]);
+    ebrake.resize(3);
+    assert_eq!(ebrake.samples, 3);
+    assert_eq!(ebrake.data.iter().collect::<Vec<_>>(), vec![true, false, true]);
+    assert_eq!(ebrake.failures, 1);
+    assert_eq!(ebrake.successes, 2);
+    assert!(!ebrake.should_trigger());
+
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.len(), 3);
+    assert!(ebrake.should_trigger());
+}
+
+#[test]
+/// Test that growing a window keeps its samples and waits for it to refill.
+fn it_should_keep_samples_when_growing() {
+    let mut ebrake = EBrake::new(3, 1);
+    ebrake.extend([false, false, true]);
+    assert!(ebrake.should_trigger());
+
+    ebrake.resize(5);
+    assert_eq!(ebrake.len(), 3);
+    assert_eq!(ebrake.failures, 2);
+    assert!(!ebrake.should_trigger());
+
+    ebrake.extend([true, true]);
+    assert!(ebrake.is_full());
+    assert!(ebrake.should_trigger());
+    ebrake.add_sample(true);
+    assert_eq!(ebrake.len(), 5);
+    assert_eq!(ebrake.failures, 1);
+}
+
+#[test]
+/// Test that lowering the tolerance trips the brake sooner.
+fn it_should_trip_sooner_with_a_lowered_tolerance() {
+    let mut ebrake = EBrake::new(5, 3);
+    ebrake.extend([true, true, true, false, false]);
+    assert!(!ebrake.should_trigger());
+
+    ebrake.set_tolerance(1).unwrap();
+    assert!(ebrake.should_trigger());
+}
+
+#[test]
+/// Test that raising the tolerance stops the brake from tripping.
+fn it_should_stop_tripping_with_a_raised_tolerance() {
+    let mut ebrake = EBrake::new(5, 1);
+    ebrake.extend([true, true, false, false, false]);
+    assert!(ebrake.should_trigger());
+
+    ebrake.set_tolerance(3).unwrap();
+    assert!(!ebrake.should_trigger());
+}
+
+#[test]
+/// Test that an unreachable tolerance is rejected and the old one kept.
+fn it_should_reject_an_unreachable_tolerance() {
+    let mut ebrake = EBrake::new(5, 1);
+    assert_eq!(ebrake.set_tolerance(5), Err(EBrakeError::ToleranceTooLarge));
+    assert_eq!(ebrake.tolerance, 1);
+}
+
+#[test]
+/// Test that checking an untripped brake reports that no action was taken.
+fn it_should_report_not_tripped() {
+    let mut ebrake = EBrake::new(3, 1);
+    ebrake.extend([false, true, true]);
+    assert_eq!(ebrake.trigger(&Trigger::Log), TripOutcome::NotTripped);
+    assert!(!TripOutcome::NotTripped.is_tripped());
+}
+
+#[test]
+/// Test that a non-terminating action reports that the trip was handled.
+fn it_should_report_a_handled_trip() {
+    fn ignore_trip() {}
+    static TRIGGER: Trigger = Trigger::Callback(ignore_trip);
+    let mut ebrake = EBrake::new(2, 0);
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.trigger_on_sample(true, &Trigger::Log), TripOutcome::Handled);
+    assert_eq!(ebrake.trigger(&TRIGGER), TripOutcome::Handled);
+    assert!(TripOutcome::Handled.is_tripped());
+}
+
+#[test]
+#[should_panic(expected = "Emergency brake triggered!")]
+/// Test that a terminating action does not return an outcome.
+fn it_should_not_return_from_a_terminating_trigger() {
+    let mut ebrake = EBrake::new(1, 0);
+    let _ = ebrake.trigger_on_sample(false, &Trigger::Panic);
+}
+
+#[test]
+/// Test that the display summary includes the window fill, counts and trip state.
+fn it_should_display_a_summary() {
+    let mut ebrake = EBrake::new(25, 3);
+    ebrake.extend([true, false, true, false, true]);
+    assert_eq!(ebrake.to_string(), "EBrake[5/25 samples, 2 failures, 3 successes, tolerance 3, armed]");
+
+    let mut ebrake = EBrake::new(2, 0);
+    ebrake.extend([false, false]);
+    let summary = ebrake.to_string();
+    assert!(summary.contains("2/2 samples"));
+    assert!(summary.contains("2 failures"));
+    assert!(summary.contains("tolerance 0"));
+    assert!(summary.ends_with("tripped]"));
+}
+
+#[test]
+/// Test that the window ends follow insertion and eviction.
+fn it_should_peek_at_the_window_ends() {
+    let mut ebrake = EBrake::new(3, 1);
+    assert_eq!(ebrake.peek_oldest(), None);
+    assert_eq!(ebrake.peek_newest(), None);
+
+    ebrake.extend([false, true]);
+    assert_eq!(ebrake.peek_oldest(), Some(false));
+    assert_eq!(ebrake.peek_newest(), Some(true));
+
+    ebrake.extend([true, false]);
+    assert_eq!(ebrake.len(), 3);
+    assert_eq!(ebrake.peek_oldest(), Some(true));
+    assert_eq!(ebrake.peek_newest(), Some(false));
+
+    ebrake.reset();
+    assert_eq!(ebrake.peek_newest(), None);
+}
+
+#[test]
+/// Test that the sample iterator yields the window from oldest to newest after rollover.
+fn it_should_iterate_over_the_window() {
+    let mut ebrake = EBrake::new(4, 1);
+    assert_eq!(ebrake.samples_iter().count(), 0);
+
+    ebrake.extend([true, true, false, true, false, false]);
+    assert_eq!(ebrake.samples_iter().collect::<Vec<_>>(), vec![false, true, false, false]);
+    assert_eq!(ebrake.samples_iter().rposition(|sample| sample), Some(1));
+    assert_eq!(ebrake.samples_iter().len(), ebrake.len());
+}
+
+#[test]
+/// Test that the minimum samples decide when the same failure pattern can trip.
+fn it_should_wait_for_the_minimum_samples() {
+    let pattern = [false, false, true, true];
+    for (min_samples, trips_after) in [(2, 2), (3, 3), (4, 4), (6, 6)] {
+        let mut ebrake = EBrake::new(6, 1);
+        ebrake.set_min_samples(min_samples).unwrap();
+        let mut tripped_at = None;
+        for (index, sample) in pattern.iter().cycle().take(6).enumerate() {
+            ebrake.add_sample(*sample);
+            if tripped_at.is_none() && ebrake.should_trigger() {
+                tripped_at = Some(index + 1);
+            }
+        }
+        assert_eq!(tripped_at, Some(trips_after), "min_samples {}", min_samples);
+    }
+}
+
+#[test]
+/// Test that the minimum samples default to, and are capped by, the window size.
+fn it_should_cap_the_minimum_samples_at_the_window_size() {
+    let mut ebrake = EBrake::new(5, 1);
+    assert_eq!(ebrake.min_samples(), 5);
+    assert_eq!(ebrake.set_min_samples(6), Err(EBrakeError::MinSamplesTooLarge));
+
+    ebrake.set_min_samples(4).unwrap();
+    ebrake.resize(3);
+    assert_eq!(ebrake.min_samples(), 3);
+}
+
+#[test]
+/// Test that the failure rate is measured over the recorded samples during warm-up.
+fn it_should_apply_the_failure_rate_during_warm_up() {
+    let mut ebrake = EBrake::new_with_rate(10, 0.5).unwrap();
+    ebrake.set_min_samples(4).unwrap();
+    ebrake.extend([false, true, false]);
+    assert!(!ebrake.should_trigger());
+    ebrake.add_sample(true);
+    assert!(ebrake.should_trigger());
+}
+
+#[test]
+/// Test that trip events carry the configured name, level and window statistics.
+fn it_should_label_trip_events() {
+    let mut ebrake = EBrake::builder()
+        .window_size(3)
+        .failure_threshold(1)
+        .name("checkout")
+        .log_level(tracing::Level::WARN)
+        .build()
+        .unwrap();
+    ebrake.extend([false, true, false]);
+
+    let events = capture_events(|| {
+        ebrake.trigger(&Trigger::Log);
+    });
+    let trip = events
+        .iter()
+        .find(|event| event.fields["message"] == "Emergency brake triggered!")
+        .unwrap();
+    assert_eq!(trip.level, "WARN");
+    assert_eq!(trip.fields["name"], "checkout");
+    assert_eq!(trip.fields["failures"], "2");
+    assert_eq!(trip.fields["samples"], "3");
+}
+
+#[test]
+/// Test that trip events default to the error level without a name.
+fn it_should_log_unnamed_trips_as_errors() {
+    let mut ebrake = EBrake::new(1, 0);
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.name(), None);
+    assert_eq!(ebrake.log_level(), tracing::Level::ERROR);
+
+    let events = capture_events(|| {
+        ebrake.trigger(&Trigger::Log);
+    });
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].level, "ERROR");
+    assert!(!events[0].fields.contains_key("name"));
+}
+
+#[test]
+/// Test that would_trigger leaves the brake unchanged and predicts the real insert.
+fn it_should_predict_a_trip_without_mutating() {
+    let mut plain = EBrake::new(5, 1);
+    let mut weighted = EBrake::new(5, 3);
+    weighted.add_weighted_sample(false, 3);
+    let mut rated = EBrake::new_with_rate(4, 0.5).unwrap();
+    let mut streaked = EBrake::new(8, 7);
+    streaked.set_max_streak(3);
+    let mut recovering = EBrake::new(3, 0);
+    let clock = MockClock::new();
+    recovering.set_clock(clock.clone());
+    recovering.set_cooldown(std::time::Duration::from_secs(10));
+    let mut latching = EBrake::new(3, 1);
+    latching.set_latching(true);
+    let mut latching_recovering = EBrake::new(3, 0);
+    latching_recovering.set_clock(clock.clone());
+    latching_recovering.set_cooldown(std::time::Duration::from_secs(10));
+    latching_recovering.set_latching(true);
+
+    let pattern = [false, true, false, false, true, true, false, true, false, false, false, true];
+    for ebrake in [&mut plain, &mut weighted, &mut rated, &mut streaked, &mut recovering, &mut latching, &mut latching_recovering] {
+        for (index, sample) in pattern.iter().copied().enumerate() {
+            if index == 8 {
+                clock.advance(std::time::Duration::from_secs(11));
+            }
+            let before = (ebrake.snapshot(), ebrake.failure_weight(), ebrake.current_streak(), ebrake.state());
+            let predicted = ebrake.would_trigger(sample);
+            assert_eq!(before, (ebrake.snapshot(), ebrake.failure_weight(), ebrake.current_streak(), ebrake.state()));
+
+            ebrake.add_sample(sample);
+            assert_eq!(predicted, ebrake.should_trigger(), "sample {} of {}", index, ebrake);
+        }
+    }
+
+    // Once latched, a success does not clear the trip.
+    assert!(latching.is_latched());
+    assert!(latching.would_trigger(true));
+    latching.add_samples(&[true; 3]);
+    assert!(latching.should_trigger());
+}
+
+#[test]
+/// Test that the counters match the window contents after heavy churn.
+fn it_should_keep_counters_consistent_under_churn() {
+    let mut ebrake = EBrake::new(7, 3);
+    for i in 0..100_000usize {
+        match i % 5 {
+            0 => ebrake.add_weighted_sample(false, i % 3),
+            1 => ebrake.add_sample(i % 2 == 0),
+            2 => ebrake.add_samples(&[true, false, false]),
+            _ => ebrake.add_sample(true),
+        }
+        assert_eq!(ebrake.current_failures() + ebrake.current_successes(), ebrake.len());
+    }
+    let failures = ebrake.samples_iter().filter(|sample| !sample).count();
+    assert_eq!(ebrake.current_failures(), failures);
+    assert_eq!(ebrake.current_successes(), ebrake.len() - failures);
+}
+
+#[test]
+/// Test that an enormous failure weight saturates instead of overflowing.
+fn it_should_saturate_the_failure_weight() {
+    let mut ebrake = EBrake::new(3, 1);
+    ebrake.add_weighted_sample(false, usize::MAX);
+    ebrake.add_weighted_sample(false, usize::MAX);
+    ebrake.add_sample(true);
+    assert_eq!(ebrake.failure_weight(), usize::MAX);
+    assert!(ebrake.should_trigger());
+}
+
+#[cfg(unix)]
+#[test]
+/// Test that the signal trigger delivers the signal to the current process.
+fn it_should_raise_the_signal_trigger() {
+    static SIGNALLED: AtomicBool = AtomicBool::new(false);
+    extern "C" fn on_signal(_: libc::c_int) {
+        SIGNALLED.store(true, Ordering::SeqCst);
+    }
+
+    let handler: extern "C" fn(libc::c_int) = on_signal;
+    // SAFETY: the handler only stores to an atomic, which is async-signal-safe.
+    unsafe { libc::signal(libc::SIGUSR1, handler as libc::sighandler_t) };
+    let mut ebrake = EBrake::new(1, 0);
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.trigger(&Trigger::Signal(libc::SIGUSR1)), TripOutcome::Handled);
+    assert!(SIGNALLED.load(Ordering::SeqCst));
+}
+
+#[test]
+/// Test that the lifetime totals accumulate across window rollovers.
+fn it_should_accumulate_lifetime_totals() {
+    let mut ebrake = EBrake::new(3, 0);
+    ebrake.extend([true, true, false, false, true]);
+    assert_eq!(ebrake.total_trips(), 1);
+
+    ebrake.extend([true, true, true, false]);
+    assert_eq!(ebrake.len(), 3);
+    assert_eq!(ebrake.total_samples(), 9);
+    assert_eq!(ebrake.total_trips(), 2);
+
+    ebrake.add_samples(&[true; 5]);
+    ebrake.reset();
+    let snapshot = ebrake.snapshot();
+    assert_eq!(snapshot.total_samples, 14);
+    assert_eq!(snapshot.total_trips, 2);
+}
+
+#[test]
+/// Test that triggers are copied rather than moved.
+fn it_should_copy_triggers() {
+    fn assert_copy<T: Copy>(_: T) {}
+    fn ignore_trip() {}
+
+    for trigger in [Trigger::Abort, Trigger::Panic, Trigger::Callback(ignore_trip), Trigger::Exit(3), Trigger::Log] {
+        let copied = trigger;
+        assert_copy(trigger);
+        assert_eq!(copied, trigger);
+    }
+
+    let mut ebrake = EBrake::builder().window_size(1).default_trigger(Trigger::Log).build().unwrap();
+    let trigger = *ebrake.default_trigger();
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.trigger(&trigger), TripOutcome::Handled);
+}
+
+#[test]
+/// Test that the core types can be shared across threads and web workers.
+fn it_should_share_the_core_types() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<EBrake>();
+    assert_send_sync::<EBrakeN<4>>();
+    assert_send_sync::<EBrakeError>();
+    assert_send_sync::<BrakeConfig>();
+    assert_send_sync::<BrakeSnapshot>();
+    assert_send_sync::<Trigger>();
+    assert_send_sync::<TripOutcome>();
+}
+
+#[test]
+/// Test that to_vec returns the window oldest first without changing it.
+fn it_should_copy_the_window_to_a_vec() {
+    let mut ebrake = EBrake::new(4, 1);
+    ebrake.extend([false, true, true, false, true]);
+    assert_eq!(ebrake.to_vec(), [true, true, false, true]);
+    assert_eq!(ebrake.len(), 4);
+    assert_eq!(ebrake.current_failures(), 1);
+}
+
+#[test]
+/// Test that drain returns the window oldest first and resets the counters.
+fn it_should_drain_the_window() {
+    let mut ebrake = EBrake::new(3, 0);
+    ebrake.add_weighted_sample(false, 3);
+    ebrake.extend([true, false]);
+    assert!(ebrake.should_trigger());
+
+    assert_eq!(ebrake.drain(), [false, true, false]);
+    assert!(ebrake.is_empty());
+    assert_eq!(ebrake.current_failures(), 0);
+    assert_eq!(ebrake.current_successes(), 0);
+    assert_eq!(ebrake.failure_weight(), 0);
+    assert_eq!(ebrake.current_streak(), 0);
+    assert!(!ebrake.should_trigger());
+    assert!(ebrake.drain().is_empty());
+}
+
+#[test]
+/// Test that the count threshold waits for the minimum number of samples.
+fn it_should_hold_the_count_threshold_for_a_partial_window() {
+    let mut ebrake = EBrake::new(5, 1);
+    ebrake.extend([false, false, false, false]);
+    assert!(!ebrake.is_warmed_up());
+    assert!(!ebrake.should_trigger());
+    assert!(ebrake.would_trigger(true));
+
+    ebrake.set_min_samples(3).unwrap();
+    assert!(ebrake.is_warmed_up());
+    assert!(ebrake.should_trigger());
+}
+
+#[test]
+/// Test that the failure rate waits for the minimum number of samples.
+fn it_should_hold_the_failure_rate_for_a_partial_window() {
+    let mut ebrake = EBrake::new_with_rate(4, 0.5).unwrap();
+    ebrake.extend([false, false, false]);
+    assert!(!ebrake.should_trigger());
+    ebrake.add_sample(true);
+    assert!(ebrake.should_trigger());
+}
+
+#[test]
+/// Test that the decayed score waits for the minimum number of samples.
+fn it_should_hold_the_decayed_score_for_a_partial_window() {
+    let mut ebrake = EBrake::new(4, 1);
+    ebrake.set_decay(0.9).unwrap();
+    ebrake.extend([false, false, false]);
+    assert!(ebrake.decayed_failure_score() > 1.0);
+    assert!(!ebrake.should_trigger());
+    ebrake.add_sample(false);
+    assert!(ebrake.should_trigger());
+}
+
+#[test]
+/// Test that the success rate waits for the minimum number of samples.
+fn it_should_hold_the_success_rate_for_a_partial_window() {
+    let mut ebrake = EBrake::new(4, 3);
+    ebrake.set_min_success_rate(0.9).unwrap();
+    ebrake.add_sample(false);
+    assert!(!ebrake.should_trigger());
+    ebrake.extend([true, true, true]);
+    assert!(ebrake.should_trigger());
+}
+
+#[test]
+/// Test that the failure streak trips a partial window.
+fn it_should_trip_a_partial_window_on_a_streak() {
+    let mut ebrake = EBrake::new(10, 5);
+    ebrake.set_max_streak(2);
+    ebrake.extend([false, false]);
+    assert!(!ebrake.is_warmed_up());
+    assert!(ebrake.should_trigger());
+    assert!(ebrake.trigger(&Trigger::Log).is_tripped());
+}
+
+#[test]
+/// Test that an empty window never trips on its thresholds, even without a minimum.
+fn it_should_not_trip_an_empty_window() {
+    let mut ebrake = EBrake::new_with_rate(4, 0.5).unwrap();
+    ebrake.set_min_samples(0).unwrap();
+    ebrake.set_min_success_rate(1.0).unwrap();
+    assert!(!ebrake.is_warmed_up());
+    assert!(!ebrake.should_trigger());
+    ebrake.add_sample(true);
+    assert!(!ebrake.should_trigger());
+}
+
+#[cfg(feature = "log-backend")]
+#[test]
+/// Test that trip and sample events are emitted as log records with the log backend.
+fn it_should_emit_log_records() {
+    struct CaptureLogger(Mutex<Vec<(log::Level, String)>>);
+
+    impl log::Log for CaptureLogger {
+        fn enabled(&self, _: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            self.0.lock().unwrap().push((record.level(), record.args().to_string()));
+        }
+
+        fn flush(&self) {}
+    }
+
+    // Log records are only emitted while no tracing subscriber has been set,
+    // so the logger is installed in a fresh process.
+    if !in_subprocess() {
+        assert!(run_in_subprocess("test::it_should_emit_log_records").success());
+        return;
+    }
+
+    static LOGGER: CaptureLogger = CaptureLogger(Mutex::new(Vec::new()));
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let mut ebrake = EBrake::new(2, 0);
+    ebrake.set_name("checkout");
+    ebrake.extend([true, false]);
+    ebrake.trigger(&Trigger::Log);
+
+    let records = LOGGER.0.lock().unwrap();
+    let (level, message) = records.iter().find(|(_, message)| message.starts_with("Emergency brake triggered!")).unwrap();
+    assert_eq!(*level, log::Level::Error);
+    assert!(message.contains(r#"name="checkout""#), "{}", message);
+    assert!(message.contains("failures=1"), "{}", message);
+    let samples = records.iter().filter(|(level, message)| *level == log::Level::Trace && message.starts_with("Emergency brake sample recorded"));
+    assert_eq!(samples.count(), 2);
+}
+
+#[test]
+/// Test that a combined trigger runs each of its actions in order.
+fn it_should_run_every_action_of_a_combined_trigger() {
+    static ORDER: Mutex<Vec<&str>> = Mutex::new(Vec::new());
+    fn first() {
+        ORDER.lock().unwrap().push("first");
+    }
+    fn second() {
+        ORDER.lock().unwrap().push("second");
+    }
+    static TRIGGER: Trigger = Trigger::All(&[Trigger::Log, Trigger::Callback(first), Trigger::Callback(second)]);
+
+    let mut ebrake = EBrake::new(1, 0);
+    assert_eq!(ebrake.trigger(&TRIGGER), TripOutcome::NotTripped);
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.trigger(&TRIGGER), TripOutcome::Handled);
+    assert_eq!(*ORDER.lock().unwrap(), ["first", "second"]);
+}
+
+#[test]
+#[should_panic(expected = "Emergency brake triggered!")]
+/// Test that a terminating action ends a combined trigger.
+fn it_should_stop_a_combined_trigger_at_a_terminating_action() {
+    fn unreachable_action() {
+        unreachable!("the panic should have ended the sequence");
+    }
+    let mut ebrake = EBrake::new(1, 0);
+    ebrake.add_sample(false);
+    ebrake.trigger(&Trigger::All(&[Trigger::Log, Trigger::Panic, Trigger::Callback(unreachable_action)]));
+}
+
+#[test]
+/// Test that brakes built through different paths compare equal when their windows match.
+fn it_should_compare_brakes_by_their_window() {
+    let mut added = EBrake::new(3, 1);
+    added.extend([false, false, true, true, false]);
+    let mut batched = EBrake::builder().window_size(3).failure_threshold(1).name("batched").build().unwrap();
+    batched.add_samples(&[true, true, false]);
+    assert_eq!(added, batched);
+
+    let collected: EBrake = [true, true, false].into_iter().collect();
+    assert_ne!(added, collected);
+    batched.add_sample(true);
+    assert_ne!(added, batched);
+    assert_ne!(EBrake::new(3, 1), EBrake::new(3, 2));
+}
+
+static DEFAULT_FIRED: AtomicUsize = AtomicUsize::new(0);
+
+fn record_default() {
+    DEFAULT_FIRED.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test]
+/// Test that the default trigger methods honor a default trigger replaced at runtime.
+fn it_should_switch_the_default_trigger_at_runtime() {
+    let mut ebrake = EBrake::builder().window_size(2).failure_threshold(0).default_trigger(Trigger::Log).build().unwrap();
+    assert_eq!(ebrake.trigger_on_sample_default(false), TripOutcome::NotTripped);
+    assert_eq!(ebrake.trigger_on_sample_default(false), TripOutcome::Handled);
+    assert_eq!(DEFAULT_FIRED.load(Ordering::SeqCst), 0);
+
+    ebrake.set_default_trigger(Trigger::Callback(record_default));
+    assert_eq!(ebrake.default_trigger(), &Trigger::Callback(record_default));
+    assert_eq!(ebrake.trigger_on_sample_default(false), TripOutcome::Handled);
+    assert_eq!(ebrake.trigger_default(), TripOutcome::Handled);
+    assert_eq!(DEFAULT_FIRED.load(Ordering::SeqCst), 2);
+}
+
+static BY_VALUE_FIRED: AtomicUsize = AtomicUsize::new(0);
+
+fn record_by_value() {
+    BY_VALUE_FIRED.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test]
+/// Test that the non-terminating triggers can be passed by value.
+fn it_should_trigger_with_a_trigger_by_value() {
+    let mut ebrake = EBrake::new(2, 0);
+    assert_eq!(ebrake.trigger_on_sample_with(true, Trigger::Log), TripOutcome::NotTripped);
+    assert_eq!(ebrake.trigger_on_sample_with(false, Trigger::Return), TripOutcome::Handled);
+    assert_eq!(ebrake.trigger_with(Trigger::Log), TripOutcome::Handled);
+    assert_eq!(ebrake.trigger_with(Trigger::Callback(record_by_value)), TripOutcome::Handled);
+    assert_eq!(ebrake.trigger_with(Trigger::All(&[Trigger::Log, Trigger::Callback(record_by_value)])), TripOutcome::Handled);
+    assert_eq!(BY_VALUE_FIRED.load(Ordering::SeqCst), 2);
+
+    let mut fixed = EBrakeN::<1>::new(0);
+    assert_eq!(fixed.trigger_on_sample_with(false, Trigger::Return), TripOutcome::Handled);
+    let shared = SharedEBrake::new(EBrake::new(1, 0));
+    assert_eq!(shared.trigger_on_sample_with(true, Trigger::Panic), TripOutcome::NotTripped);
+    assert_eq!(shared.trigger_on_sample_with(false, Trigger::Log), TripOutcome::Handled);
+    assert_eq!(shared.trigger_with(Trigger::Return), TripOutcome::Handled);
+}
+
+#[cfg(unix)]
+#[test]
+/// Test that a signal trigger can be passed by value.
+fn it_should_raise_a_signal_trigger_by_value() {
+    static SIGNALLED: AtomicBool = AtomicBool::new(false);
+    extern "C" fn on_signal(_: libc::c_int) {
+        SIGNALLED.store(true, Ordering::SeqCst);
+    }
+
+    let handler: extern "C" fn(libc::c_int) = on_signal;
+    // SAFETY: the handler only stores to an atomic, which is async-signal-safe.
+    unsafe { libc::signal(libc::SIGUSR2, handler as libc::sighandler_t) };
+    let mut ebrake = EBrake::new(1, 0);
+    assert_eq!(ebrake.trigger_on_sample_with(false, Trigger::Signal(libc::SIGUSR2)), TripOutcome::Handled);
+    assert!(SIGNALLED.load(Ordering::SeqCst));
+}
+
+#[test]
+#[should_panic(expected = "Emergency brake triggered!")]
+/// Test that a panic trigger can be passed by value.
+fn it_should_panic_with_a_trigger_by_value() {
+    let mut ebrake = EBrake::new(1, 0);
+    ebrake.trigger_on_sample_with(false, Trigger::Panic);
+}
+
+#[test]
+/// Test that an exit trigger can be passed by value.
+fn it_should_exit_with_a_trigger_by_value() {
+    if in_subprocess() {
+        let mut ebrake = EBrake::new(1, 0);
+        ebrake.trigger_on_sample_with(false, Trigger::Exit(43));
+        unreachable!("the exit trigger should have terminated the process");
+    }
+
+    let status = run_in_subprocess("test::it_should_exit_with_a_trigger_by_value");
+    assert_eq!(status.code(), Some(43));
+}
+
+#[test]
+/// Test that an abort trigger can be passed by value.
+fn it_should_abort_with_a_trigger_by_value() {
+    if in_subprocess() {
+        let mut ebrake = EBrake::new(1, 0);
+        ebrake.add_sample(false);
+        ebrake.trigger_with(Trigger::Abort);
+        unreachable!("the abort trigger should have terminated the process");
+    }
+
+    let status = run_in_subprocess("test::it_should_abort_with_a_trigger_by_value");
+    assert!(!status.success());
+    #[cfg(unix)]
+    assert_eq!(std::os::unix::process::ExitStatusExt::signal(&status), Some(libc::SIGABRT));
+}
+
+#[test]
+/// Test that the cached warm-up flag matches the computed condition as the window changes.
+fn it_should_keep_the_warm_up_flag_current() {
+    fn assert_warm_up(ebrake: &EBrake) {
+        assert_eq!(ebrake.is_warmed_up(), ebrake.has_min_samples(ebrake.len()), "{}", ebrake);
+        let window_tripped = ebrake.has_min_samples(ebrake.len()) && ebrake.failure_weight() > ebrake.tolerance;
+        assert_eq!(ebrake.should_trigger(), window_tripped, "{}", ebrake);
+    }
+
+    let mut ebrake = EBrake::new(6, 2);
+    for (index, sample) in crate::test_util::random_samples(200, 0x5eed).enumerate() {
+        ebrake.add_sample(sample);
+        assert_warm_up(&ebrake);
+        match index {
+            20 => ebrake.set_min_samples(3).unwrap(),
+            40 => ebrake.resize(9),
+            60 => ebrake.resize(4),
+            80 => ebrake.reset(),
+            100 => drop(ebrake.drain()),
+            120 => ebrake.set_min_samples(4).unwrap(),
+            140 => ebrake = ebrake.merge(&EBrake::new(3, 0)),
+            _ => {},
+        }
+        #[cfg(feature = "serde")]
+        if index == 160 {
+            ebrake = serde_json::from_str(&serde_json::to_string(&ebrake).unwrap()).unwrap();
+        }
+        assert_warm_up(&ebrake);
+    }
+}
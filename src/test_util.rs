@@ -0,0 +1,15 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+/// A small deterministic pseudo-random sequence of samples, three quarters
+/// of which are successes. It does not allocate, so `no_std` tests can use it.
+pub(crate) fn random_samples(count: usize, mut seed: u64) -> impl Iterator<Item = bool> {
+    (0..count).map(move |_| {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        seed & 0b11 != 0
+    })
+}
@@ -0,0 +1,135 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// The time windowed emergency brake holds the samples recorded within a
+/// trailing window of time, rather than a fixed number of samples. Samples
+/// older than the window duration are evicted regardless of how many samples
+/// have been recorded.
+/// ```
+/// use emergency_brake::*;
+/// use std::time::Duration;
+/// let mut ebrake = TimeWindowedEBrake::new(Duration::from_secs(60), 3);
+/// ebrake.add_sample(false);
+/// assert_eq!(ebrake.trigger(&Trigger::Panic), TripOutcome::NotTripped);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TimeWindowedEBrake {
+    clock: Arc<dyn Clock>,
+    data: VecDeque<(Instant, bool)>,
+    failures: usize,
+    successes: usize,
+    tolerance: usize,
+    window: Duration,
+}
+
+impl TimeWindowedEBrake {
+    /// Creates a new time windowed Emergency Brake with the given window duration and tolerance.
+    pub fn new(window: Duration, tolerance: usize) -> Self {
+        TimeWindowedEBrake::with_clock(window, tolerance, SystemClock)
+    }
+
+    /// Creates a new time windowed Emergency Brake that reads time from the given clock.
+    pub fn with_clock(window: Duration, tolerance: usize, clock: impl Clock + 'static) -> Self {
+        TimeWindowedEBrake {
+            clock: Arc::new(clock),
+            data: VecDeque::new(),
+            failures: 0,
+            successes: 0,
+            tolerance,
+            window,
+        }
+    }
+
+    /// Returns the number of failures within the window.
+    pub fn current_failures(&self) -> usize {
+        self.failures - self.expired().filter(|sample| !sample).count()
+    }
+
+    /// Returns the number of successes within the window.
+    pub fn current_successes(&self) -> usize {
+        self.successes - self.expired().filter(|sample| *sample).count()
+    }
+
+    /// Evicts all samples that are older than the window duration.
+    pub fn evict_expired(&mut self) {
+        let now = self.clock.now();
+        while let Some((recorded, sample)) = self.data.front() {
+            if now.duration_since(*recorded) < self.window {
+                break;
+            }
+            match sample {
+                true => self.successes -= 1,
+                false => self.failures -= 1,
+            }
+            self.data.pop_front();
+        }
+    }
+
+    /// Returns the samples at the front of the window that have expired but
+    /// have not yet been evicted.
+    fn expired(&self) -> impl Iterator<Item = bool> + '_ {
+        let now = self.clock.now();
+        self.data
+            .iter()
+            .take_while(move |(recorded, _)| now.duration_since(*recorded) >= self.window)
+            .map(|(_, sample)| *sample)
+    }
+}
+
+impl EmergencyBrake for TimeWindowedEBrake {
+    fn add_sample(&mut self, sample: bool) {
+        self.evict_expired();
+
+        match sample {
+            true => self.successes += 1,
+            false => self.failures += 1,
+        }
+
+        self.data.push_back((self.clock.now(), sample));
+    }
+
+    fn should_trigger(&self) -> bool {
+        self.current_failures() > self.tolerance
+    }
+
+    fn trigger(&self, trigger: &Trigger) -> TripOutcome {
+        match self.should_trigger() {
+            true => {
+                error!(failures = self.current_failures(), window = ?self.window, "Emergency brake triggered!");
+                trigger.execute()
+            },
+            false => TripOutcome::NotTripped,
+        }
+    }
+
+    fn trigger_abort(&self) -> bool {
+        self.trigger(&Trigger::Abort).is_tripped()
+    }
+
+    fn trigger_panic(&self) -> bool {
+        self.trigger(&Trigger::Panic).is_tripped()
+    }
+
+    fn trigger_on_sample(&mut self, sample: bool, trigger: &Trigger) -> TripOutcome {
+        self.add_sample(sample);
+        self.trigger(trigger)
+    }
+
+    fn reset(&mut self) {
+        self.data.clear();
+        self.failures = 0;
+        self.successes = 0;
+    }
+}
+
+
+/// Test module for the TimeWindowedEBrake.
+#[cfg(test)]
+mod test;
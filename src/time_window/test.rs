@@ -0,0 +1,62 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+use std::time::Duration;
+
+#[test]
+/// Test that failures within the window trigger the brake.
+fn it_should_trigger_on_failures_within_the_window() {
+    let clock = MockClock::new();
+    let mut ebrake = TimeWindowedEBrake::with_clock(Duration::from_secs(10), 2, clock.clone());
+    for _ in 0..3 {
+        ebrake.add_sample(false);
+        clock.advance(Duration::from_secs(1));
+    }
+    assert_eq!(ebrake.current_failures(), 3);
+    assert!(ebrake.should_trigger());
+}
+
+#[test]
+/// Test that failures older than the window no longer count, even before eviction.
+fn it_should_ignore_expired_failures() {
+    let clock = MockClock::new();
+    let mut ebrake = TimeWindowedEBrake::with_clock(Duration::from_secs(10), 2, clock.clone());
+    for _ in 0..3 {
+        ebrake.add_sample(false);
+    }
+    assert!(ebrake.should_trigger());
+
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(ebrake.current_failures(), 0);
+    assert!(!ebrake.should_trigger());
+}
+
+#[test]
+/// Test that adding a sample evicts expired entries regardless of sample count.
+fn it_should_evict_expired_samples_on_insert() {
+    let clock = MockClock::new();
+    let mut ebrake = TimeWindowedEBrake::with_clock(Duration::from_secs(10), 2, clock.clone());
+    ebrake.add_sample(false);
+    ebrake.add_sample(true);
+    clock.advance(Duration::from_secs(6));
+    ebrake.add_sample(false);
+    clock.advance(Duration::from_secs(6));
+    ebrake.add_sample(true);
+
+    assert_eq!(ebrake.data.len(), 2);
+    assert_eq!(ebrake.failures, 1);
+    assert_eq!(ebrake.successes, 1);
+}
+
+#[test]
+/// Test that resetting clears the time window.
+fn it_should_reset_the_time_window() {
+    let mut ebrake = TimeWindowedEBrake::new(Duration::from_secs(10), 0);
+    ebrake.add_sample(false);
+    ebrake.reset();
+    assert_eq!(ebrake.data.len(), 0);
+    assert!(!ebrake.should_trigger());
+}
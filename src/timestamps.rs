@@ -0,0 +1,93 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+use std::time::Instant;
+
+/// The instants at which the samples in the window were recorded, and the
+/// timing of the most recent trip.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SampleTimes {
+    // Samples restored without their timestamps are padded with `None`.
+    times: VecDeque<Option<Instant>>,
+    tripped_at: Option<Instant>,
+    time_to_trip: Option<Duration>,
+}
+
+impl SampleTimes {
+    /// Records the instant of the newest sample, for a window now holding `len` samples.
+    pub(crate) fn push_back(&mut self, len: usize, now: Instant) {
+        while self.times.len() + 1 < len {
+            self.times.push_front(None);
+        }
+        self.times.push_back(Some(now));
+    }
+
+    pub(crate) fn pop_front(&mut self) {
+        self.times.pop_front();
+    }
+
+    pub(crate) fn clear_window(&mut self) {
+        self.times.clear();
+    }
+
+    pub(crate) fn clear(&mut self) {
+        *self = SampleTimes::default();
+    }
+}
+
+impl EBrake {
+    /// Returns how long ago the first failure of the current failure streak
+    /// was recorded, or `None` if the newest sample is not a failure. A streak
+    /// longer than the window is measured from its oldest failure still in
+    /// the window. Time is read from the brake's clock.
+    /// ```
+    /// use emergency_brake::*;
+    /// use std::time::Duration;
+    /// let clock = MockClock::new();
+    /// let mut ebrake = EBrake::builder().window_size(5).failure_threshold(1).clock(clock.clone()).build().unwrap();
+    /// ebrake.add_sample(false);
+    /// clock.advance(Duration::from_secs(3));
+    /// ebrake.add_sample(false);
+    /// assert_eq!(ebrake.time_since_first_failure(), Some(Duration::from_secs(3)));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "timestamps")))]
+    pub fn time_since_first_failure(&self) -> Option<Duration> {
+        let times = &self.times.times;
+        let streak = self.failure_streak.min(times.len());
+        if streak == 0 {
+            return None;
+        }
+        let first = times[times.len() - streak]?;
+        Some(self.clock.now().saturating_duration_since(first))
+    }
+
+    /// Returns the instant at which the brake last tripped, if it has tripped
+    /// since it was created or reset.
+    #[cfg_attr(docsrs, doc(cfg(feature = "timestamps")))]
+    pub fn tripped_at(&self) -> Option<Instant> {
+        self.times.tripped_at
+    }
+
+    /// Returns how long the brake took to trip the last time it tripped,
+    /// measured from the first failure of the streak that ended in the trip.
+    /// This is `None` if a trip did not end a failure streak, such as a trip
+    /// on a success rate.
+    #[cfg_attr(docsrs, doc(cfg(feature = "timestamps")))]
+    pub fn time_to_trip(&self) -> Option<Duration> {
+        self.times.time_to_trip
+    }
+
+    /// Records the timing of a trip that has just happened.
+    pub(crate) fn record_trip_time(&mut self) {
+        self.times.time_to_trip = self.time_since_first_failure();
+        self.times.tripped_at = Some(self.clock.now());
+    }
+}
+
+/// Test module for the sample timestamps.
+#[cfg(test)]
+mod test;
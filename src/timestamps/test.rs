@@ -0,0 +1,69 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+fn timed_brake(clock: &MockClock) -> EBrake {
+    let mut ebrake = EBrake::new(4, 1);
+    ebrake.set_clock(clock.clone());
+    ebrake
+}
+
+#[test]
+/// Test that the time since the first failure of the streak is measured from the mock clock.
+fn it_should_measure_the_current_failure_streak() {
+    let clock = MockClock::new();
+    let mut ebrake = timed_brake(&clock);
+    ebrake.add_sample(false);
+    clock.advance(Duration::from_secs(1));
+    ebrake.add_sample(true);
+    assert_eq!(ebrake.time_since_first_failure(), None);
+
+    clock.advance(Duration::from_secs(2));
+    ebrake.add_sample(false);
+    clock.advance(Duration::from_secs(5));
+    ebrake.add_sample(false);
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(ebrake.time_since_first_failure(), Some(Duration::from_secs(6)));
+}
+
+#[test]
+/// Test that the trip records when it happened and how long the streak took.
+fn it_should_record_the_time_to_trip() {
+    let clock = MockClock::new();
+    let mut ebrake = timed_brake(&clock);
+    ebrake.extend([true, true]);
+    assert_eq!(ebrake.tripped_at(), None);
+
+    ebrake.add_sample(false);
+    clock.advance(Duration::from_millis(1500));
+    let tripped_at = clock.now();
+    ebrake.add_sample(false);
+    assert!(ebrake.should_trigger());
+    assert_eq!(ebrake.tripped_at(), Some(tripped_at));
+    assert_eq!(ebrake.time_to_trip(), Some(Duration::from_millis(1500)));
+
+    // The trip timing is kept while the brake stays tripped.
+    clock.advance(Duration::from_secs(10));
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.tripped_at(), Some(tripped_at));
+
+    ebrake.reset();
+    assert_eq!(ebrake.tripped_at(), None);
+    assert_eq!(ebrake.time_to_trip(), None);
+}
+
+#[test]
+/// Test that a streak longer than the window is measured from its oldest failure in the window.
+fn it_should_measure_a_streak_longer_than_the_window() {
+    let clock = MockClock::new();
+    let mut ebrake = timed_brake(&clock);
+    for _ in 0..6 {
+        ebrake.add_sample(false);
+        clock.advance(Duration::from_secs(1));
+    }
+    assert_eq!(ebrake.current_streak(), 6);
+    assert_eq!(ebrake.time_since_first_failure(), Some(Duration::from_secs(4)));
+}
@@ -0,0 +1,55 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+impl EBrake {
+    /// Sets the minimum interval between trips. A new trip that starts within
+    /// the interval of the previous one is suppressed: `should_trigger` and
+    /// every trigger method report the brake as not tripped until the interval
+    /// has elapsed, even though the failures exceed the tolerance. Unlike the
+    /// trigger rate limit, this also holds back terminal triggers such as
+    /// `Abort`, so a brake in a restart loop cannot amplify into a storm of
+    /// restarts. The interval is measured with the brake's clock, from the
+    /// sample that started the previous trip, and is not reset by `reset`.
+    /// ```
+    /// use emergency_brake::*;
+    /// use std::time::Duration;
+    /// let mut ebrake = EBrake::new(1, 0);
+    /// ebrake.set_min_trip_interval(Duration::from_secs(300));
+    /// ebrake.add_sample(false);
+    /// assert!(ebrake.should_trigger());
+    ///
+    /// ebrake.add_sample(true);
+    /// ebrake.add_sample(false);
+    /// assert!(!ebrake.should_trigger());
+    /// ```
+    pub fn set_min_trip_interval(&mut self, interval: Duration) {
+        self.min_trip_interval = Some(interval);
+    }
+
+    /// Removes the minimum interval between trips.
+    pub fn clear_min_trip_interval(&mut self) {
+        self.min_trip_interval = None;
+    }
+
+    /// Returns the minimum interval between trips, if one is set.
+    pub fn min_trip_interval(&self) -> Option<Duration> {
+        self.min_trip_interval
+    }
+
+    /// Returns true if a new trip would start within the minimum interval of
+    /// the previous one. A trip that is already under way is never suppressed.
+    pub(crate) fn within_trip_interval(&self) -> bool {
+        match (self.min_trip_interval, self.last_trip_at) {
+            (Some(interval), Some(last)) => !self.last_tripped && self.clock.now().saturating_duration_since(last) < interval,
+            _ => false,
+        }
+    }
+}
+
+/// Test module for the minimum trip interval.
+#[cfg(test)]
+mod test;
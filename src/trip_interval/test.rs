@@ -0,0 +1,71 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+fn interval_brake(clock: &MockClock) -> EBrake {
+    let mut ebrake = EBrake::new(2, 0);
+    ebrake.set_clock(clock.clone());
+    ebrake.set_min_trip_interval(Duration::from_secs(60));
+    ebrake
+}
+
+#[test]
+/// Test that a trip within the interval of the previous one is suppressed.
+fn it_should_suppress_a_trip_within_the_interval() {
+    let clock = MockClock::new();
+    let mut ebrake = interval_brake(&clock);
+    ebrake.extend([true, false]);
+    assert!(ebrake.should_trigger());
+    assert!(ebrake.trigger_on_sample(false, &Trigger::Log).is_tripped());
+    assert_eq!(ebrake.total_trips(), 1);
+
+    ebrake.extend([true, true]);
+    clock.advance(Duration::from_secs(30));
+    ebrake.add_sample(false);
+    assert!(!ebrake.should_trigger());
+    assert!(!ebrake.would_trigger(false));
+    assert_eq!(ebrake.trigger(&Trigger::Panic), TripOutcome::NotTripped);
+    assert!(!ebrake.trigger_abort());
+    assert_eq!(ebrake.total_trips(), 1);
+}
+
+#[test]
+/// Test that a suppressed trip fires once the interval has elapsed.
+fn it_should_fire_after_the_interval_elapses() {
+    let clock = MockClock::new();
+    let mut ebrake = interval_brake(&clock);
+    ebrake.extend([true, false]);
+    ebrake.reset();
+
+    clock.advance(Duration::from_secs(59));
+    ebrake.extend([false, false]);
+    assert!(!ebrake.should_trigger());
+
+    clock.advance(Duration::from_secs(1));
+    assert!(ebrake.should_trigger());
+    ebrake.add_sample(false);
+    assert_eq!(ebrake.total_trips(), 2);
+
+    // The interval now runs from the second trip.
+    ebrake.extend([true, true]);
+    clock.advance(Duration::from_secs(59));
+    ebrake.add_sample(false);
+    assert!(!ebrake.should_trigger());
+}
+
+#[test]
+/// Test that clearing the interval stops suppressing trips.
+fn it_should_clear_the_interval() {
+    let clock = MockClock::new();
+    let mut ebrake = interval_brake(&clock);
+    assert_eq!(ebrake.min_trip_interval(), Some(Duration::from_secs(60)));
+    ebrake.extend([false, false, true, true, false]);
+    assert!(!ebrake.should_trigger());
+
+    ebrake.clear_min_trip_interval();
+    assert_eq!(ebrake.min_trip_interval(), None);
+    assert!(ebrake.should_trigger());
+}
@@ -0,0 +1,105 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+/// The Outcome trait classifies a rich sample type as a success or a failure.
+pub trait Outcome {
+    /// Returns true if the outcome counts as a failure.
+    fn is_failure(&self) -> bool;
+}
+
+impl Outcome for bool {
+    fn is_failure(&self) -> bool {
+        !self
+    }
+}
+
+impl<T, E> Outcome for Result<T, E> {
+    fn is_failure(&self) -> bool {
+        self.is_err()
+    }
+}
+
+/// The typed emergency brake records samples of any type that implements
+/// `Outcome`. Each sample is classified into the underlying EBrake, and the
+/// typed values in the window are kept for inspection.
+/// ```
+/// use emergency_brake::*;
+/// let mut ebrake = TypedEBrake::new(3, 1);
+/// ebrake.add_sample(Ok::<u16, &str>(200));
+/// ebrake.add_sample(Err("connection reset"));
+/// assert_eq!(ebrake.values().last(), Some(&Err("connection reset")));
+/// assert_eq!(ebrake.brake().current_failures(), 1);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TypedEBrake<T> {
+    brake: EBrake,
+    values: VecDeque<T>,
+}
+
+impl<T: Outcome> TypedEBrake<T> {
+    /// Creates a new typed Emergency Brake with the given number of samples and tolerance.
+    pub fn new(samples: usize, tolerance: usize) -> Self {
+        TypedEBrake::from_brake(EBrake::new(samples, tolerance))
+    }
+
+    /// Creates a typed Emergency Brake around a configured EBrake.
+    /// Any samples already in the EBrake are discarded.
+    pub fn from_brake(mut brake: EBrake) -> Self {
+        brake.reset();
+        TypedEBrake {
+            brake,
+            values: VecDeque::new(),
+        }
+    }
+
+    /// Classifies and inserts a sample, evicting the oldest sample if the window is full.
+    pub fn add_sample(&mut self, sample: T) {
+        self.brake.add_sample(!sample.is_failure());
+        self.values.push_back(sample);
+        while self.values.len() > self.brake.len() {
+            self.values.pop_front();
+        }
+    }
+
+    /// Returns true if the emergency brake should be triggered.
+    pub fn should_trigger(&self) -> bool {
+        self.brake.should_trigger()
+    }
+
+    /// Returns `TripOutcome::NotTripped` if the emergency brake has not been triggered.
+    /// If the emergency brake has been triggered, the supplied trigger action will be executed.
+    pub fn trigger(&self, trigger: &Trigger) -> TripOutcome {
+        self.brake.trigger(trigger)
+    }
+
+    /// Insert a sample and check if the emergency brake should be triggered.
+    pub fn trigger_on_sample(&mut self, sample: T, trigger: &Trigger) -> TripOutcome {
+        self.add_sample(sample);
+        self.trigger(trigger)
+    }
+
+    /// Clear the sample window and the typed values.
+    pub fn reset(&mut self) {
+        self.brake.reset();
+        self.values.clear();
+    }
+
+    /// Returns the typed values in the window, from oldest to newest.
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        self.values.iter()
+    }
+
+    /// Returns the underlying EBrake holding the classified samples.
+    pub fn brake(&self) -> &EBrake {
+        &self.brake
+    }
+}
+
+
+/// Test module for the TypedEBrake.
+#[cfg(test)]
+mod test;
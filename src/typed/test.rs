@@ -0,0 +1,56 @@
+/*
+ *  Kelsea Blackwell (c) 2023
+ *  See LICENSE for licensing information.
+ */
+
+use super::*;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Response {
+    Success,
+    Retryable,
+    Fatal,
+}
+
+impl Outcome for Response {
+    fn is_failure(&self) -> bool {
+        *self == Response::Fatal
+    }
+}
+
+#[test]
+/// Test that a custom outcome type is classified into the window.
+fn it_should_classify_a_custom_outcome() {
+    let mut ebrake = TypedEBrake::new(4, 1);
+    for response in [Response::Success, Response::Retryable, Response::Fatal, Response::Retryable] {
+        ebrake.add_sample(response);
+    }
+    assert_eq!(ebrake.brake().current_failures(), 1);
+    assert_eq!(ebrake.brake().current_successes(), 3);
+    assert!(!ebrake.should_trigger());
+
+    assert!(ebrake.trigger_on_sample(Response::Fatal, &Trigger::Log).is_tripped());
+}
+
+#[test]
+/// Test that the typed values roll over with the window.
+fn it_should_evict_typed_values_with_the_window() {
+    let mut ebrake = TypedEBrake::new(2, 1);
+    ebrake.add_sample(Response::Fatal);
+    ebrake.add_sample(Response::Success);
+    ebrake.add_sample(Response::Retryable);
+    assert_eq!(ebrake.values().cloned().collect::<Vec<_>>(), vec![Response::Success, Response::Retryable]);
+    assert_eq!(ebrake.brake().current_failures(), 0);
+
+    ebrake.reset();
+    assert_eq!(ebrake.values().len(), 0);
+}
+
+#[test]
+/// Test that results and booleans are built-in outcomes.
+fn it_should_classify_results_and_booleans() {
+    assert!(Err::<(), _>("timeout").is_failure());
+    assert!(!Ok::<_, ()>(200).is_failure());
+    assert!(false.is_failure());
+    assert!(!true.is_failure());
+}